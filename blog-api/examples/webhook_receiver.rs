@@ -0,0 +1,61 @@
+// A local endpoint for trying out webhooks. It verifies each delivery's signature, prints
+// the event and answers with a configurable status code, so retries can be tested too.
+//
+//     cargo run --example webhook_receiver -- <secret> [port] [status]
+//
+// Then subscribe http://127.0.0.1:<port>/ (default 9000) with the same secret.
+use axum::{
+    body::Bytes,
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Router,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let secret = args.next().ok_or_else(|| anyhow::anyhow!("usage: webhook_receiver <secret> [port] [status]"))?;
+    let port: u16 = args.next().map(|p| p.parse()).transpose()?.unwrap_or(9000);
+    let status = StatusCode::from_u16(args.next().map(|s| s.parse()).transpose()?.unwrap_or(200))?;
+
+    let app = Router::new().route(
+        "/",
+        post(move |headers: HeaderMap, body: Bytes| async move { receive(&secret, status, &headers, &body) }),
+    );
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+    println!("Listening for webhooks on http://127.0.0.1:{}/", port);
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
+
+fn receive(secret: &str, status: StatusCode, headers: &HeaderMap, body: &[u8]) -> StatusCode {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).unwrap_or_default();
+
+    let timestamp = header("x-webhook-timestamp");
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    let valid = header("x-webhook-signature")
+        .strip_prefix("sha256=")
+        .and_then(|sig| hex::decode(sig).ok())
+        .is_some_and(|sig| mac.verify_slice(&sig).is_ok());
+
+    println!(
+        "{} delivery {} ({}): {}",
+        header("x-webhook-event"),
+        header("x-webhook-delivery"),
+        if valid { "signature ok" } else { "BAD SIGNATURE" },
+        String::from_utf8_lossy(body)
+    );
+
+    if valid {
+        status
+    } else {
+        StatusCode::UNAUTHORIZED
+    }
+}
\ No newline at end of file
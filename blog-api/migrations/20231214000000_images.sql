@@ -0,0 +1,27 @@
+-- Create the images table. Uploads start as 'pending' and are processed in the background.
+CREATE TABLE IF NOT EXISTS images (
+    id UUID PRIMARY KEY,
+    uploader_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'processing', 'ready', 'failed')),
+    width INTEGER,
+    height INTEGER,
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create the image_variants table, one row per generated size and format
+CREATE TABLE IF NOT EXISTS image_variants (
+    image_id UUID NOT NULL REFERENCES images(id) ON DELETE CASCADE,
+    name VARCHAR(20) NOT NULL,
+    format VARCHAR(10) NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    storage_key VARCHAR(255) NOT NULL UNIQUE,
+    PRIMARY KEY (image_id, name, format)
+);
+
+-- Create indexes
+CREATE INDEX idx_images_uploader ON images(uploader_id);
+CREATE INDEX idx_images_status ON images(status);
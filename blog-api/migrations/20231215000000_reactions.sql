@@ -0,0 +1,43 @@
+-- Create the post_reactions table, one row per user, post and reaction kind
+CREATE TABLE IF NOT EXISTS post_reactions (
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(32) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (post_id, user_id, kind)
+);
+
+CREATE INDEX idx_post_reactions_user ON post_reactions(user_id, post_id);
+
+-- Running totals per post and kind, so reading counts never scans post_reactions
+CREATE TABLE IF NOT EXISTS post_reaction_counts (
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    kind VARCHAR(32) NOT NULL,
+    count BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (post_id, kind)
+);
+
+CREATE OR REPLACE FUNCTION update_post_reaction_counts()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO post_reaction_counts (post_id, kind, count, updated_at)
+        VALUES (NEW.post_id, NEW.kind, 1, NOW())
+        ON CONFLICT (post_id, kind)
+        DO UPDATE SET count = post_reaction_counts.count + 1, updated_at = NOW();
+        RETURN NEW;
+    ELSE
+        -- Rows are kept at zero so updated_at still reflects the last change
+        UPDATE post_reaction_counts
+        SET count = count - 1, updated_at = NOW()
+        WHERE post_id = OLD.post_id AND kind = OLD.kind;
+        RETURN OLD;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER post_reactions_count
+AFTER INSERT OR DELETE ON post_reactions
+FOR EACH ROW
+EXECUTE FUNCTION update_post_reaction_counts();
\ No newline at end of file
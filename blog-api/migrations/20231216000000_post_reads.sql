@@ -0,0 +1,10 @@
+-- Create the post_reads table, one row per post a user has read
+CREATE TABLE IF NOT EXISTS post_reads (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    read_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, post_id)
+);
+
+-- Create indexes
+CREATE INDEX idx_post_reads_post ON post_reads(post_id);
\ No newline at end of file
@@ -0,0 +1,26 @@
+-- Create the reading_lists table
+CREATE TABLE IF NOT EXISTS reading_lists (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    -- Shared lists can be read by anyone with the link, private ones only by the owner
+    shared BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Create the reading_list_items table, ordered by position within a list
+CREATE TABLE IF NOT EXISTS reading_list_items (
+    list_id UUID NOT NULL REFERENCES reading_lists(id) ON DELETE CASCADE,
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (list_id, post_id),
+    -- Deferrable, so a reorder can swap positions within a single statement
+    CONSTRAINT reading_list_items_position_key UNIQUE (list_id, position) DEFERRABLE INITIALLY IMMEDIATE
+);
+
+-- Create indexes
+CREATE INDEX idx_reading_lists_owner ON reading_lists(owner_id);
+CREATE INDEX idx_reading_list_items_post ON reading_list_items(post_id);
\ No newline at end of file
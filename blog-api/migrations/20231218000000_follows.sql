@@ -0,0 +1,14 @@
+-- Create the follows table, one row per follower and followed author
+CREATE TABLE IF NOT EXISTS follows (
+    follower_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    followee_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (follower_id, followee_id),
+    CHECK (follower_id <> followee_id)
+);
+
+-- Create indexes
+CREATE INDEX idx_follows_followee ON follows(followee_id);
+
+-- Lets the personalized feed read each followed author's latest posts straight from the index
+CREATE INDEX idx_posts_author_published_created ON posts(author_id, created_at DESC, id DESC) WHERE published = true;
\ No newline at end of file
@@ -0,0 +1,27 @@
+-- Create the notifications table. Events of the same kind about the same subject are
+-- coalesced into one unread row, e.g. "alice and 4 others reacted to your post".
+CREATE TABLE IF NOT EXISTS notifications (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    recipient_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(32) NOT NULL,
+    post_id UUID REFERENCES posts(id) ON DELETE CASCADE,
+    -- Identifies what events are coalesced on, such as "reaction:<post id>"
+    group_key VARCHAR(100) NOT NULL,
+    -- Users who caused the notification, most recent first
+    actor_ids UUID[] NOT NULL,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one unread notification per group, which new events are folded into
+CREATE UNIQUE INDEX idx_notifications_unread_group ON notifications(recipient_id, group_key) WHERE read_at IS NULL;
+CREATE INDEX idx_notifications_recipient ON notifications(recipient_id, updated_at DESC);
+
+-- Notification kinds a user has muted
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(32) NOT NULL,
+    muted BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (user_id, kind)
+);
\ No newline at end of file
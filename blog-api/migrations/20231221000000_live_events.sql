@@ -0,0 +1,3 @@
+-- Ids for live events sent with NOTIFY. Every instance relays the same ids to its SSE
+-- clients, so a client can resume with Last-Event-ID on whichever instance it reconnects to.
+CREATE SEQUENCE IF NOT EXISTS live_event_id_seq;
\ No newline at end of file
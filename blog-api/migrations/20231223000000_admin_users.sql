@@ -0,0 +1,3 @@
+-- Administrators can manage site-wide settings such as webhook subscriptions. There is no
+-- endpoint for granting it; set it directly in the database.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS is_admin BOOLEAN NOT NULL DEFAULT FALSE;
\ No newline at end of file
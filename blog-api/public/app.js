@@ -0,0 +1,149 @@
+// Read state is kept on the server once a user signs in, and in localStorage otherwise
+function currentUserId() {
+    return localStorage.getItem('userId');
+}
+
+function apiHeaders() {
+    const userId = currentUserId();
+    return userId ? { 'X-User-Id': userId } : {};
+}
+
+// Function to store read status
+function saveReadStatus(postId, isRead) {
+    if (currentUserId()) {
+        return fetch(`/api/posts/${postId}/read`, {
+            method: isRead ? 'PUT' : 'DELETE',
+            headers: apiHeaders()
+        });
+    }
+    const readPosts = JSON.parse(localStorage.getItem('readPosts') || '{}');
+    readPosts[postId] = isRead;
+    localStorage.setItem('readPosts', JSON.stringify(readPosts));
+}
+
+// Function to get read status for a post returned by the API
+function getReadStatus(post) {
+    if (currentUserId()) {
+        return post.read === true;
+    }
+    const readPosts = JSON.parse(localStorage.getItem('readPosts') || '{}');
+    return readPosts[post.id] === true;
+}
+
+// On first sign-in, hand the posts read on this device over to the server
+function syncLocalReadState() {
+    const readPosts = JSON.parse(localStorage.getItem('readPosts') || '{}');
+    const postIds = Object.keys(readPosts).filter(id => readPosts[id] === true);
+    if (postIds.length === 0) {
+        localStorage.removeItem('readPosts');
+        return Promise.resolve();
+    }
+    return fetch('/api/posts/read', {
+        method: 'POST',
+        headers: { ...apiHeaders(), 'Content-Type': 'application/json' },
+        body: JSON.stringify({ post_ids: postIds })
+    }).then(response => {
+        if (response.ok) {
+            localStorage.removeItem('readPosts');
+        }
+    });
+}
+
+function loadPosts() {
+    const postsContainer = document.getElementById('posts');
+
+    fetch('/api/posts', { headers: apiHeaders() })
+        .then(response => {
+            if (!response.ok) {
+                throw new Error('Network response was not ok');
+            }
+            return response.json();
+        })
+        .then(posts => {
+            postsContainer.innerHTML = '';
+
+            if (posts.length === 0) {
+                postsContainer.innerHTML = '<p>No posts found.</p>';
+                return;
+            }
+
+            posts.forEach(post => {
+                const isRead = getReadStatus(post);
+                const postDate = new Date(post.created_at).toLocaleDateString();
+                const postEl = document.createElement('div');
+                postEl.className = `post ${isRead ? 'read' : ''}`;
+                postEl.setAttribute('data-post-id', post.id);
+                postEl.innerHTML = `
+                    <h2>${post.title}</h2>
+                    <div class="post-meta">
+                        <span>Posted on ${postDate}</span>
+                        <span>by Author ID: ${post.author_id}</span>
+                    </div>
+                    <div class="post-content">${post.content}</div>
+                    <button class="read-toggle ${isRead ? 'read' : ''}">
+                        ${isRead ? 'Mark as Unread' : 'Mark as Read'}
+                    </button>
+                `;
+                postsContainer.appendChild(postEl);
+            });
+
+            // Add event listeners to toggle read status
+            document.querySelectorAll('.read-toggle').forEach(button => {
+                button.addEventListener('click', function() {
+                    const postEl = this.closest('.post');
+                    const postId = postEl.getAttribute('data-post-id');
+                    const isCurrentlyRead = postEl.classList.contains('read');
+                    const newReadStatus = !isCurrentlyRead;
+
+                    // Toggle classes
+                    postEl.classList.toggle('read');
+                    this.classList.toggle('read');
+
+                    // Update button text
+                    this.textContent = newReadStatus ? 'Mark as Unread' : 'Mark as Read';
+
+                    // Save to the server or localStorage
+                    saveReadStatus(postId, newReadStatus);
+                });
+            });
+        })
+        .catch(error => {
+            console.error('Error fetching posts:', error);
+            postsContainer.innerHTML = `
+                <div class="error">
+                    Failed to load posts. Make sure the API server is running.
+                </div>
+            `;
+        });
+}
+
+document.addEventListener('DOMContentLoaded', function() {
+    const sessionForm = document.getElementById('session');
+    const userIdInput = document.getElementById('user-id');
+    userIdInput.value = currentUserId() || '';
+
+    sessionForm.addEventListener('submit', function(event) {
+        event.preventDefault();
+        const userId = userIdInput.value.trim();
+        if (userId) {
+            localStorage.setItem('userId', userId);
+            syncLocalReadState().then(loadPosts);
+        } else {
+            localStorage.removeItem('userId');
+            loadPosts();
+        }
+    });
+
+    loadPosts();
+
+    // Reload the list when posts change on the server. EventSource reconnects on
+    // its own and resumes from the last event it saw.
+    const liveEvents = new EventSource('/api/events');
+    let reloadTimer;
+    ['post.created', 'post.updated', 'post.published', 'post.deleted'].forEach(name => {
+        liveEvents.addEventListener(name, function() {
+            clearTimeout(reloadTimer);
+            reloadTimer = setTimeout(loadPosts, 250);
+        });
+    });
+});
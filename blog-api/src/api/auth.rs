@@ -0,0 +1,97 @@
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    http::{request::Parts, HeaderValue},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::UserRepository;
+use crate::errors::AppError;
+
+// Header carrying the id of the acting user. There is no session handling yet,
+// so this stands in for an authenticated identity.
+pub const USER_ID_HEADER: &str = "x-user-id";
+
+// The user making the request, taken from the `X-User-Id` header.
+// Use `MaybeUser` for endpoints that also serve anonymous readers.
+#[derive(Debug, Clone, Copy)]
+pub struct CurrentUser(pub Uuid);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for CurrentUser
+where
+    PgPool: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let value = parts
+            .headers
+            .get(USER_ID_HEADER)
+            .ok_or_else(|| AppError::Unauthorized("Missing X-User-Id header".to_string()))?;
+        let user_id = parse_user_id(value).ok_or_else(|| AppError::Unauthorized("Invalid X-User-Id header".to_string()))?;
+
+        verify(&PgPool::from_ref(state), user_id).await
+    }
+}
+
+// The user making the request if there is one. A missing header means an anonymous reader,
+// but a malformed header is a 400 and an unknown user a 401 rather than being treated as
+// anonymous, and database errors are passed on.
+#[derive(Debug, Clone, Copy)]
+pub struct MaybeUser(pub Option<CurrentUser>);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for MaybeUser
+where
+    PgPool: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(USER_ID_HEADER) else {
+            return Ok(MaybeUser(None));
+        };
+        let user_id = parse_user_id(value).ok_or_else(|| AppError::BadRequest("Invalid X-User-Id header".to_string()))?;
+
+        Ok(MaybeUser(Some(verify(&PgPool::from_ref(state), user_id).await?)))
+    }
+}
+
+// A user with `users.is_admin` set, for endpoints that manage the whole site
+#[derive(Debug, Clone, Copy)]
+pub struct AdminUser;
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminUser
+where
+    PgPool: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let CurrentUser(user_id) = CurrentUser::from_request_parts(parts, state).await?;
+        if !UserRepository::new(PgPool::from_ref(state)).is_admin(user_id).await? {
+            return Err(AppError::Forbidden("Only administrators can do this".to_string()));
+        }
+
+        Ok(AdminUser)
+    }
+}
+
+fn parse_user_id(value: &HeaderValue) -> Option<Uuid> {
+    value.to_str().ok().and_then(|v| Uuid::parse_str(v.trim()).ok())
+}
+
+async fn verify(pool: &PgPool, user_id: Uuid) -> Result<CurrentUser, AppError> {
+    let repo = UserRepository::new(pool.clone());
+    if repo.find_by_id(user_id).await?.is_none() {
+        return Err(AppError::Unauthorized(format!("User with id {} does not exist", user_id)));
+    }
+
+    Ok(CurrentUser(user_id))
+}
\ No newline at end of file
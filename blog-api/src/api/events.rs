@@ -0,0 +1,89 @@
+use std::convert::Infallible;
+use std::time::Duration;
+
+use axum::{
+    extract::{FromRef, Query, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::get,
+    Router,
+};
+use futures_util::stream::{self, Stream, StreamExt};
+use serde::Deserialize;
+use sqlx::PgPool;
+use tokio::sync::broadcast::error::RecvError;
+use uuid::Uuid;
+
+use super::auth::{CurrentUser, MaybeUser};
+use crate::config::AppConfig;
+use crate::errors::{AppError, Result};
+use crate::events::{Events, LiveEvent};
+
+#[derive(Clone, FromRef)]
+struct EventsState {
+    pool: PgPool,
+    events: Events,
+    keep_alive: Duration,
+}
+
+pub fn create_router(pool: PgPool, events: Events, config: &AppConfig) -> Router {
+    let keep_alive = Duration::from_secs(config.events.keep_alive_secs.max(1));
+
+    Router::new()
+        .route("/", get(stream_events))
+        .with_state(EventsState { pool, events, keep_alive })
+}
+
+#[derive(Debug, Deserialize)]
+struct EventFilter {
+    author_id: Option<Uuid>,
+}
+
+impl EventFilter {
+    // Events for drafts only go to the draft's author
+    fn matches(&self, event: &LiveEvent, viewer: Option<CurrentUser>) -> bool {
+        let visible = event.published || viewer.is_some_and(|CurrentUser(id)| id == event.author_id);
+        visible && self.author_id.is_none_or(|id| id == event.author_id)
+    }
+}
+
+async fn stream_events(
+    State(events): State<Events>,
+    State(keep_alive): State<Duration>,
+    MaybeUser(viewer): MaybeUser,
+    headers: HeaderMap,
+    Query(filter): Query<EventFilter>,
+) -> Result<Sse<impl Stream<Item = std::result::Result<Event, Infallible>>>> {
+    let last_event_id = match headers.get("Last-Event-ID") {
+        Some(value) => Some(
+            value
+                .to_str()
+                .ok()
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .ok_or_else(|| AppError::BadRequest("Last-Event-ID must be an event id".to_string()))?,
+        ),
+        None => None,
+    };
+
+    let (replay, receiver) = events.subscribe(last_event_id);
+
+    // A client that falls too far behind is disconnected; browsers reconnect on their own
+    // and the Last-Event-ID they send replays what they missed from the buffer
+    let live = stream::unfold(receiver, |mut receiver| async move {
+        match receiver.recv().await {
+            Ok(event) => Some((event, receiver)),
+            Err(RecvError::Lagged(skipped)) => {
+                tracing::debug!("Dropping an SSE client that fell {} events behind", skipped);
+                None
+            }
+            Err(RecvError::Closed) => None,
+        }
+    });
+
+    let stream = stream::iter(replay)
+        .chain(live)
+        .filter(move |event| std::future::ready(filter.matches(event, viewer)))
+        .map(|event| Ok(Event::default().id(event.id.to_string()).event(&event.event).data(&event.data)));
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::new().interval(keep_alive)))
+}
\ No newline at end of file
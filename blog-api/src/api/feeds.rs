@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    handler::Handler,
+    http::header,
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use chrono::Utc;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::db::{PostRepository, UserRepository};
+use crate::errors::{AppError, Result};
+use crate::feed::{self, Entry, Feed};
+use crate::middleware::http_cache::LastModified;
+use crate::middleware::HttpCacheLayer;
+
+// Number of posts included in each feed
+const FEED_SIZE: i64 = 20;
+
+#[derive(Clone)]
+struct FeedState {
+    pool: PgPool,
+    site: Arc<SiteInfo>,
+}
+
+struct SiteInfo {
+    title: String,
+    description: String,
+    base_url: String,
+}
+
+pub fn create_router(pool: PgPool, config: &AppConfig) -> Router {
+    let state = FeedState {
+        pool,
+        site: Arc::new(SiteInfo {
+            title: config.site.title.clone(),
+            description: config.site.description.clone(),
+            base_url: config.site.base_url().to_string(),
+        }),
+    };
+    let cache = HttpCacheLayer::new(&config.http_cache.feeds, &config.http_cache.drafts);
+
+    Router::new()
+        .route("/rss.xml", get(rss.layer(cache.clone())))
+        .route("/atom.xml", get(atom.layer(cache.clone())))
+        .route("/feed.json", get(json_feed.layer(cache.clone())))
+        .route("/users/:id/rss.xml", get(user_rss.layer(cache.clone())))
+        .route("/users/:id/atom.xml", get(user_atom.layer(cache.clone())))
+        .route("/users/:id/feed.json", get(user_json_feed.layer(cache)))
+        .with_state(state)
+}
+
+async fn rss(State(state): State<FeedState>) -> Result<impl IntoResponse> {
+    let feed = load_feed(&state, None, "rss.xml").await?;
+    Ok(render_xml(&feed, feed::rss::CONTENT_TYPE, feed::rss::render))
+}
+
+async fn atom(State(state): State<FeedState>) -> Result<impl IntoResponse> {
+    let feed = load_feed(&state, None, "atom.xml").await?;
+    Ok(render_xml(&feed, feed::atom::CONTENT_TYPE, feed::atom::render))
+}
+
+async fn json_feed(State(state): State<FeedState>) -> Result<impl IntoResponse> {
+    let feed = load_feed(&state, None, "feed.json").await?;
+    render_json(&feed)
+}
+
+async fn user_rss(State(state): State<FeedState>, Path(id): Path<Uuid>) -> Result<impl IntoResponse> {
+    let feed = load_feed(&state, Some(id), "rss.xml").await?;
+    Ok(render_xml(&feed, feed::rss::CONTENT_TYPE, feed::rss::render))
+}
+
+async fn user_atom(State(state): State<FeedState>, Path(id): Path<Uuid>) -> Result<impl IntoResponse> {
+    let feed = load_feed(&state, Some(id), "atom.xml").await?;
+    Ok(render_xml(&feed, feed::atom::CONTENT_TYPE, feed::atom::render))
+}
+
+async fn user_json_feed(State(state): State<FeedState>, Path(id): Path<Uuid>) -> Result<impl IntoResponse> {
+    let feed = load_feed(&state, Some(id), "feed.json").await?;
+    render_json(&feed)
+}
+
+fn render_xml(feed: &Feed, content_type: &'static str, render: fn(&Feed) -> String) -> impl IntoResponse {
+    (
+        LastModified(feed.updated),
+        [(header::CONTENT_TYPE, content_type)],
+        render(feed),
+    )
+}
+
+fn render_json(feed: &Feed) -> Result<impl IntoResponse> {
+    let body = feed::json::render(feed).map_err(|e| AppError::InternalError(e.to_string()))?;
+    Ok((
+        LastModified(feed.updated),
+        [(header::CONTENT_TYPE, feed::json::CONTENT_TYPE)],
+        body,
+    ))
+}
+
+// Builds the site-wide feed, or a single author's feed when `author_id` is set.
+// Only published posts are ever included.
+async fn load_feed(state: &FeedState, author_id: Option<Uuid>, file_name: &str) -> Result<Feed> {
+    let site = &state.site;
+    let post_repo = PostRepository::new(state.pool.clone());
+    let user_repo = UserRepository::new(state.pool.clone());
+
+    let (posts, title, feed_url) = match author_id {
+        Some(author_id) => {
+            let author = user_repo
+                .find_by_id(author_id)
+                .await?
+                .ok_or_else(|| AppError::NotFoundError(format!("User with id {} not found", author_id)))?;
+            let posts = post_repo.find_by_author(author_id, FEED_SIZE, 0, true).await?;
+            let title = format!("{} - {}", site.title, author.username);
+            let feed_url = format!("{}/feeds/users/{}/{}", site.base_url, author_id, file_name);
+            (posts, title, feed_url)
+        }
+        None => {
+            let posts = post_repo.list(FEED_SIZE, 0, true).await?;
+            let feed_url = format!("{}/feeds/{}", site.base_url, file_name);
+            (posts, site.title.clone(), feed_url)
+        }
+    };
+
+    let mut author_ids: Vec<Uuid> = posts.iter().map(|p| p.author_id).collect();
+    author_ids.sort_unstable();
+    author_ids.dedup();
+    let authors: HashMap<Uuid, String> = user_repo
+        .find_by_ids(&author_ids)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u.username))
+        .collect();
+
+    // An empty feed has nothing to date it by, so fall back to the current time
+    let updated = posts.iter().map(|p| p.updated_at).max().unwrap_or_else(Utc::now);
+
+    let entries = posts
+        .into_iter()
+        .map(|post| Entry {
+            url: format!("{}/posts/{}", site.base_url, post.id),
+            author_name: authors.get(&post.author_id).cloned().unwrap_or_default(),
+            id: post.id,
+            title: post.title,
+            content: post.content,
+            published: post.created_at,
+            updated: post.updated_at,
+        })
+        .collect();
+
+    Ok(Feed {
+        title,
+        description: site.description.clone(),
+        site_url: site.base_url.clone(),
+        feed_url,
+        updated,
+        entries,
+    })
+}
@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{FromRef, Path, Query, State},
+    handler::Handler,
+    http::header,
+    response::IntoResponse,
+    routing::{get, put},
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::auth::CurrentUser;
+use super::posts::build_responses;
+use super::users::with_follow_counts;
+use crate::config::{AppConfig, ReactionsConfig};
+use crate::db::{FollowRepository, PostRepository, UserRepository};
+use crate::errors::{AppError, Result};
+use crate::middleware::http_cache::Visibility;
+use crate::middleware::HttpCacheLayer;
+use crate::models::post::PostPage;
+use crate::models::user::UserResponse;
+use crate::notifications::{Event, Notifier};
+
+const MAX_FEED_LIMIT: i64 = 100;
+
+#[derive(Clone, FromRef)]
+struct FollowerState {
+    pool: PgPool,
+    notifier: Notifier,
+}
+
+#[derive(Clone, FromRef)]
+struct FollowState {
+    pool: PgPool,
+    reactions: Arc<ReactionsConfig>,
+}
+
+// Mounted under /api/users next to the user routes
+pub fn create_router(pool: PgPool, notifier: Notifier) -> Router {
+    Router::new()
+        .route("/:id/follow", put(follow).delete(unfollow))
+        .with_state(FollowerState { pool, notifier })
+}
+
+// The personalized feed, mounted at /api/feed
+pub fn create_feed_router(pool: PgPool, config: &AppConfig) -> Router {
+    let cache = HttpCacheLayer::new(&config.http_cache.lists, &config.http_cache.drafts);
+    let state = FollowState {
+        pool,
+        reactions: Arc::new(config.reactions.clone()),
+    };
+
+    Router::new()
+        .route("/", get(feed.layer(cache)))
+        .with_state(state)
+}
+
+// Following someone twice is a no-op; returns the followed user with updated counts
+async fn follow(
+    State(pool): State<PgPool>,
+    State(notifier): State<Notifier>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UserResponse>> {
+    if user.0 == id {
+        return Err(AppError::BadRequest("Users cannot follow themselves".to_string()));
+    }
+    let followee = UserRepository::new(pool.clone()).find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("User with id {} not found", id)))?;
+    
+    if FollowRepository::new(pool.clone()).follow(user.0, id).await? {
+        notifier.notify(Event::Followed { follower_id: user.0, followee_id: id });
+    }
+    
+    let mut response = with_follow_counts(&pool, vec![followee]).await?;
+    Ok(Json(response.remove(0)))
+}
+
+async fn unfollow(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<UserResponse>> {
+    let followee = UserRepository::new(pool.clone()).find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("User with id {} not found", id)))?;
+    
+    FollowRepository::new(pool.clone()).unfollow(user.0, id).await?;
+    
+    let mut response = with_follow_counts(&pool, vec![followee]).await?;
+    Ok(Json(response.remove(0)))
+}
+
+#[derive(Debug, Deserialize)]
+struct FeedParams {
+    #[serde(default = "default_limit")]
+    limit: i64,
+    cursor: Option<String>,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+async fn feed(
+    State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    user: CurrentUser,
+    Query(params): Query<FeedParams>,
+) -> Result<impl IntoResponse> {
+    let limit = params.limit.clamp(1, MAX_FEED_LIMIT);
+    let before = params.cursor.as_deref().map(decode_cursor).transpose()?;
+    
+    let posts = PostRepository::new(pool.clone()).find_followed(user.0, before, limit).await?;
+    
+    // A full page means there may be more; the last post marks where the next one starts
+    let next_cursor = if posts.len() as i64 == limit {
+        posts.last().map(|p| encode_cursor(p.created_at, p.id))
+    } else {
+        None
+    };
+    
+    // Listings have no Last-Modified, see `posts::list_posts`
+    let (posts, _) = build_responses(&pool, &reactions, posts, Some(user)).await?;
+    
+    Ok((
+        Visibility::Private,
+        [(header::VARY, "X-User-Id")],
+        Json(PostPage { posts, next_cursor }),
+    ))
+}
+
+fn encode_cursor(created_at: DateTime<Utc>, id: Uuid) -> String {
+    format!("{}_{}", created_at.timestamp_micros(), id.simple())
+}
+
+fn decode_cursor(cursor: &str) -> Result<(DateTime<Utc>, Uuid)> {
+    let invalid = || AppError::BadRequest("Invalid cursor".to_string());
+    let (micros, id) = cursor.split_once('_').ok_or_else(invalid)?;
+    let created_at = micros
+        .parse::<i64>()
+        .ok()
+        .and_then(DateTime::from_timestamp_micros)
+        .ok_or_else(invalid)?;
+    let id = Uuid::parse_str(id).map_err(|_| invalid())?;
+    
+    Ok((created_at, id))
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{Request, Response};
+use axum::{
+    extract::State,
+    response::Html,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+
+use crate::config::AppConfig;
+use crate::graphql::BlogSchema;
+use crate::middleware::RateLimitClient;
+
+pub fn create_router(schema: BlogSchema, config: &AppConfig) -> Router {
+    // GraphiQL is a development aid; production config turns it off
+    let route = if config.graphql.graphiql {
+        get(graphiql).post(execute)
+    } else {
+        post(execute)
+    };
+
+    Router::new().route("/", route).with_state(schema)
+}
+
+// One operation per request. Batches aren't accepted, since the depth and complexity limits
+// apply to each operation and a batch could be made arbitrarily long.
+async fn execute(
+    State(schema): State<BlogSchema>,
+    client: Option<Extension<RateLimitClient>>,
+    Json(request): Json<Request>,
+) -> Json<Response> {
+    // Lets mutations charge the rate limit buckets of their REST routes
+    let request = match client {
+        Some(Extension(client)) => request.data(client),
+        None => request,
+    };
+    Json(schema.execute(request).await)
+}
+
+async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").title("Blog API GraphiQL").finish())
+}
\ No newline at end of file
@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use sqlx::PgPool;
+
+use crate::config::{AppConfig, HealthConfig};
+use crate::db;
+use crate::shutdown::Shutdown;
+
+#[derive(Clone)]
+struct HealthState {
+    pool: PgPool,
+    media_dir: Arc<Path>,
+    config: Arc<HealthConfig>,
+    shutdown: Shutdown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Pass,
+    Fail,
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    status: Status,
+    latency_ms: f64,
+    // Measurements, or the reason for a failure
+    #[serde(flatten)]
+    details: Map<String, Value>,
+}
+
+#[derive(Debug, Serialize)]
+struct HealthReport {
+    status: Status,
+    checks: BTreeMap<&'static str, Check>,
+}
+
+impl IntoResponse for HealthReport {
+    fn into_response(self) -> axum::response::Response {
+        let code = match self.status {
+            Status::Pass => StatusCode::OK,
+            Status::Fail => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        (code, [(header::CACHE_CONTROL, "no-store")], Json(self)).into_response()
+    }
+}
+
+pub fn create_router(pool: PgPool, config: &AppConfig, shutdown: Shutdown) -> Router {
+    let state = HealthState {
+        pool,
+        media_dir: Arc::from(PathBuf::from(&config.media.directory)),
+        config: Arc::new(config.health.clone()),
+        shutdown,
+    };
+
+    Router::new()
+        .route("/health/live", get(live))
+        .route("/health/ready", get(ready))
+        .with_state(state)
+}
+
+// The process is up and its runtime is scheduling tasks; dependencies are not checked
+async fn live() -> HealthReport {
+    let runtime = timed(async {
+        tokio::task::yield_now().await;
+        Ok(Map::new())
+    })
+    .await;
+
+    report([("runtime", runtime)])
+}
+
+// Whether this instance should receive traffic
+async fn ready(State(state): State<HealthState>) -> HealthReport {
+    let timeout = Duration::from_millis(state.config.database_timeout_ms);
+
+    let database = timed(with_timeout(timeout, async {
+        sqlx::query("SELECT 1").execute(&state.pool).await?;
+        Ok(Map::new())
+    }));
+
+    let migrations = timed(with_timeout(timeout, async {
+        let (applied, expected) = db::migration_status(&state.pool).await?;
+        let details = details(json!({ "applied": applied, "expected": expected }));
+        if applied < expected {
+            return Err(CheckError(format!("{} of {} migrations applied", applied, expected), details));
+        }
+        Ok(details)
+    }));
+
+    let min_free = state.config.min_free_disk_bytes;
+    let media_dir = state.media_dir.clone();
+    let disk = timed(async move {
+        let (available, total) = tokio::task::spawn_blocking(move || disk_space(&media_dir)).await??;
+        let details = details(json!({ "available_bytes": available, "total_bytes": total }));
+        if available < min_free {
+            return Err(CheckError("Media directory is low on disk space".to_string(), details));
+        }
+        Ok(details)
+    });
+
+    let (database, migrations, disk) = tokio::join!(database, migrations, disk);
+    let mut checks = vec![("database", database), ("migrations", migrations), ("media_disk", disk)];
+
+    if state.shutdown.is_draining() {
+        let mut details = Map::new();
+        details.insert("message".to_string(), "Server is shutting down".into());
+        checks.push(("shutdown", Check { status: Status::Fail, latency_ms: 0.0, details }));
+    }
+
+    report(checks)
+}
+
+fn report(checks: impl IntoIterator<Item = (&'static str, Check)>) -> HealthReport {
+    let checks: BTreeMap<_, _> = checks.into_iter().collect();
+    let status = if checks.values().all(|c| c.status == Status::Pass) {
+        Status::Pass
+    } else {
+        Status::Fail
+    };
+    HealthReport { status, checks }
+}
+
+// A failed check carries a message and, optionally, the measurements that failed it
+struct CheckError(String, Map<String, Value>);
+
+impl<E: ToString> From<E> for CheckError {
+    fn from(e: E) -> Self {
+        CheckError(e.to_string(), Map::new())
+    }
+}
+
+async fn timed<F>(check: F) -> Check
+where
+    F: Future<Output = Result<Map<String, Value>, CheckError>>,
+{
+    let started = Instant::now();
+    let result = check.await;
+    let latency_ms = (started.elapsed().as_secs_f64() * 1_000_000.0).round() / 1000.0;
+
+    match result {
+        Ok(details) => Check { status: Status::Pass, latency_ms, details },
+        Err(CheckError(message, mut details)) => {
+            details.insert("message".to_string(), message.into());
+            Check { status: Status::Fail, latency_ms, details }
+        }
+    }
+}
+
+async fn with_timeout<F>(timeout: Duration, check: F) -> Result<Map<String, Value>, CheckError>
+where
+    F: Future<Output = Result<Map<String, Value>, CheckError>>,
+{
+    tokio::time::timeout(timeout, check)
+        .await
+        .map_err(|_| CheckError(format!("Timed out after {} ms", timeout.as_millis()), Map::new()))?
+}
+
+fn details(value: Value) -> Map<String, Value> {
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+// Returns (available, total) bytes on the file system holding `dir`
+#[cfg(unix)]
+fn disk_space(dir: &Path) -> std::io::Result<(u64, u64)> {
+    let stat = rustix::fs::statvfs(dir)?;
+    Ok((stat.f_bavail * stat.f_frsize, stat.f_blocks * stat.f_frsize))
+}
+
+#[cfg(not(unix))]
+fn disk_space(_dir: &Path) -> std::io::Result<(u64, u64)> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "Disk space is only checked on Unix"))
+}
\ No newline at end of file
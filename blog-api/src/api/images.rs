@@ -0,0 +1,181 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{DefaultBodyLimit, Multipart, Path, Query, State},
+    handler::Handler,
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::db::{ImageRepository, UserRepository};
+use crate::errors::{AppError, Result};
+use crate::images::{self, ImagePipeline};
+use crate::middleware::HttpCacheLayer;
+use crate::models::image::ImageResponse;
+use crate::models::media::detect_content_type;
+use crate::storage::MediaStore;
+
+use super::media::read_file_field;
+
+// Room for the multipart boundaries and headers around the file itself
+const MULTIPART_OVERHEAD: usize = 64 * 1024;
+
+#[derive(Clone)]
+struct ImageState {
+    pool: PgPool,
+    store: Arc<dyn MediaStore>,
+    pipeline: ImagePipeline,
+    max_upload_bytes: usize,
+}
+
+pub fn create_router(
+    pool: PgPool,
+    store: Arc<dyn MediaStore>,
+    pipeline: ImagePipeline,
+    config: &AppConfig,
+) -> Router {
+    let max_upload_bytes = config.media.max_upload_bytes;
+    let state = ImageState { pool, store, pipeline, max_upload_bytes };
+
+    Router::new()
+        .route(
+            "/",
+            post(upload_image).layer(DefaultBodyLimit::max(max_upload_bytes + MULTIPART_OVERHEAD)),
+        )
+        .route("/:id", get(get_image).delete(delete_image))
+        .with_state(state)
+}
+
+// Serves generated variants at /images/:id/<name>.<ext>, e.g. /images/<id>/w640.webp
+pub fn create_file_router(
+    pool: PgPool,
+    store: Arc<dyn MediaStore>,
+    pipeline: ImagePipeline,
+    config: &AppConfig,
+) -> Router {
+    let state = ImageState {
+        pool,
+        store,
+        pipeline,
+        max_upload_bytes: config.media.max_upload_bytes,
+    };
+    let cache = HttpCacheLayer::new(&config.http_cache.media, &config.http_cache.drafts);
+
+    Router::new()
+        .route("/:id/:file", get(serve_variant.layer(cache)))
+        .with_state(state)
+}
+
+#[derive(Debug, Deserialize)]
+struct UploaderParam {
+    uploader_id: Uuid,
+}
+
+// Stores the original and returns 202 right away; variants are generated by the
+// image pipeline and show up once the status is "ready"
+async fn upload_image(
+    State(state): State<ImageState>,
+    // In a real app, you would get the uploader from the authenticated session
+    Query(params): Query<UploaderParam>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<ImageResponse>)> {
+    let user_repo = UserRepository::new(state.pool.clone());
+    if user_repo.find_by_id(params.uploader_id).await?.is_none() {
+        return Err(AppError::BadRequest(format!("User with id {} does not exist", params.uploader_id)));
+    }
+
+    let (_, data) = read_file_field(&mut multipart, state.max_upload_bytes).await?;
+
+    if !detect_content_type(&data).is_some_and(|t| t.starts_with("image/")) {
+        return Err(AppError::UnsupportedMediaType(
+            "Only JPEG, PNG, GIF and WebP images are accepted".to_string(),
+        ));
+    }
+
+    let id = Uuid::new_v4();
+    state
+        .store
+        .put(&images::original_key(id), data)
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to store image: {}", e)))?;
+
+    let repo = ImageRepository::new(state.pool.clone());
+    let image = match repo.create(id, params.uploader_id).await {
+        Ok(image) => image,
+        Err(e) => {
+            images::delete_files(state.store.as_ref(), id, &[]).await;
+            return Err(e);
+        }
+    };
+
+    state.pipeline.enqueue(id);
+
+    Ok((StatusCode::ACCEPTED, Json(ImageResponse::new(image, Vec::new()))))
+}
+
+async fn get_image(
+    State(state): State<ImageState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ImageResponse>> {
+    let repo = ImageRepository::new(state.pool);
+    let image = repo.find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Image with id {} not found", id)))?;
+    let variants = repo.find_variants(id).await?;
+
+    Ok(Json(ImageResponse::new(image, variants)))
+}
+
+async fn delete_image(
+    State(state): State<ImageState>,
+    Path(id): Path<Uuid>,
+    // In a real app, you would verify that the user is the uploader
+) -> Result<Json<serde_json::Value>> {
+    let repo = ImageRepository::new(state.pool);
+    let variants = repo.find_variants(id).await?;
+
+    if !repo.delete(id).await? {
+        return Err(AppError::NotFoundError(format!("Image with id {} not found", id)));
+    }
+
+    images::delete_files(state.store.as_ref(), id, &variants).await;
+
+    Ok(Json(serde_json::json!({ "message": "Image deleted successfully" })))
+}
+
+async fn serve_variant(
+    State(state): State<ImageState>,
+    Path((id, file)): Path<(Uuid, String)>,
+) -> Result<Response> {
+    let not_found = || AppError::NotFoundError(format!("Image variant {}/{} not found", id, file));
+
+    let (name, format) = match file.rsplit_once('.') {
+        Some((name, "jpg")) => (name, "jpeg"),
+        Some((name, "webp")) => (name, "webp"),
+        _ => return Err(not_found()),
+    };
+
+    let repo = ImageRepository::new(state.pool);
+    let variant = repo.find_variant(id, name, format).await?.ok_or_else(not_found)?;
+
+    let body = state
+        .store
+        .get(&variant.storage_key, 0..variant.size_bytes as u64)
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to read image file: {}", e)))?;
+
+    let content_type = if format == "jpeg" { "image/jpeg" } else { "image/webp" };
+    let mut response = ([(header::CONTENT_TYPE, content_type)], body).into_response();
+    response.headers_mut().insert(header::CONTENT_LENGTH, HeaderValue::from(variant.size_bytes));
+    // Variants are written once and never change
+    if let Ok(etag) = HeaderValue::from_str(&format!("\"{}\"", variant.storage_key)) {
+        response.headers_mut().insert(header::ETAG, etag);
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,340 @@
+use std::ops::Range;
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    extract::{multipart::MultipartError, DefaultBodyLimit, Multipart, Path, Query, State},
+    handler::Handler,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::db::media_repository::NewMedia;
+use crate::db::{MediaRepository, UserRepository};
+use crate::errors::{AppError, Result};
+use crate::middleware::http_cache::LastModified;
+use crate::middleware::HttpCacheLayer;
+use crate::models::media::{detect_content_type, MediaResponse};
+use crate::storage::MediaStore;
+
+// Room for the multipart boundaries and headers around the file itself
+const MULTIPART_OVERHEAD: usize = 64 * 1024;
+
+#[derive(Clone)]
+struct MediaState {
+    pool: PgPool,
+    store: Arc<dyn MediaStore>,
+    max_upload_bytes: usize,
+}
+
+pub fn create_router(pool: PgPool, store: Arc<dyn MediaStore>, config: &AppConfig) -> Router {
+    let max_upload_bytes = config.media.max_upload_bytes;
+    let state = MediaState { pool, store, max_upload_bytes };
+
+    Router::new()
+        .route(
+            "/",
+            post(upload_media).layer(DefaultBodyLimit::max(max_upload_bytes + MULTIPART_OVERHEAD)),
+        )
+        .route("/:id", get(get_media).delete(delete_media))
+        .with_state(state)
+}
+
+// Serves the stored files themselves, mounted at /media
+pub fn create_file_router(pool: PgPool, store: Arc<dyn MediaStore>, config: &AppConfig) -> Router {
+    let state = MediaState {
+        pool,
+        store,
+        max_upload_bytes: config.media.max_upload_bytes,
+    };
+    let cache = HttpCacheLayer::new(&config.http_cache.media, &config.http_cache.drafts);
+
+    Router::new()
+        .route("/:id", get(serve_media.layer(cache)))
+        .with_state(state)
+}
+
+#[derive(Debug, Deserialize)]
+struct UploaderParam {
+    uploader_id: Uuid,
+}
+
+async fn upload_media(
+    State(state): State<MediaState>,
+    // In a real app, you would get the uploader from the authenticated session
+    Query(params): Query<UploaderParam>,
+    mut multipart: Multipart,
+) -> Result<Json<MediaResponse>> {
+    let user_repo = UserRepository::new(state.pool.clone());
+    if user_repo.find_by_id(params.uploader_id).await?.is_none() {
+        return Err(AppError::BadRequest(format!("User with id {} does not exist", params.uploader_id)));
+    }
+
+    let (file_name, data) = read_file_field(&mut multipart, state.max_upload_bytes).await?;
+
+    let content_type = detect_content_type(&data).ok_or_else(|| {
+        AppError::UnsupportedMediaType("Only JPEG, PNG, GIF, WebP and PDF files are accepted".to_string())
+    })?;
+
+    let id = Uuid::new_v4();
+    let storage_key = id.to_string();
+    let size_bytes = data.len() as i64;
+
+    state
+        .store
+        .put(&storage_key, data)
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to store media: {}", e)))?;
+
+    let repo = MediaRepository::new(state.pool.clone());
+    let media = repo
+        .create(&NewMedia {
+            id,
+            uploader_id: params.uploader_id,
+            file_name: &file_name,
+            content_type,
+            size_bytes,
+            storage_key: &storage_key,
+        })
+        .await;
+
+    // Don't leave an orphaned file behind if the metadata couldn't be saved
+    if media.is_err() {
+        if let Err(e) = state.store.delete(&storage_key).await {
+            tracing::error!("Failed to remove media file {}: {}", storage_key, e);
+        }
+    }
+
+    Ok(Json(MediaResponse::from(media?)))
+}
+
+// Reads the "file" field of the upload, enforcing the size limit while streaming
+pub(super) async fn read_file_field(multipart: &mut Multipart, max_bytes: usize) -> Result<(String, Bytes)> {
+    while let Some(mut field) = multipart.next_field().await.map_err(multipart_error)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = sanitize_file_name(field.file_name().unwrap_or("upload"));
+        let mut data = Vec::new();
+        while let Some(chunk) = field.chunk().await.map_err(multipart_error)? {
+            if data.len() + chunk.len() > max_bytes {
+                return Err(AppError::PayloadTooLarge(format!(
+                    "Files must be at most {} bytes",
+                    max_bytes
+                )));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        if data.is_empty() {
+            return Err(AppError::BadRequest("Uploaded file is empty".to_string()));
+        }
+
+        return Ok((file_name, Bytes::from(data)));
+    }
+
+    Err(AppError::BadRequest("Missing multipart field \"file\"".to_string()))
+}
+
+// The body limit surfaces as a multipart error, so keep its 413 status
+fn multipart_error(err: MultipartError) -> AppError {
+    if err.status() == StatusCode::PAYLOAD_TOO_LARGE {
+        AppError::PayloadTooLarge("Upload exceeds the maximum allowed size".to_string())
+    } else {
+        AppError::BadRequest(err.body_text())
+    }
+}
+
+// Keeps only the final path component and caps the length to fit the column
+fn sanitize_file_name(name: &str) -> String {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or_default().trim();
+    let name: String = name.chars().filter(|c| !c.is_control()).take(255).collect();
+    if name.is_empty() {
+        "upload".to_string()
+    } else {
+        name
+    }
+}
+
+async fn get_media(
+    State(state): State<MediaState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<MediaResponse>> {
+    let repo = MediaRepository::new(state.pool);
+    let media = repo.find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Media with id {} not found", id)))?;
+
+    Ok(Json(MediaResponse::from(media)))
+}
+
+async fn delete_media(
+    State(state): State<MediaState>,
+    Path(id): Path<Uuid>,
+    // In a real app, you would verify that the user is the uploader
+) -> Result<Json<serde_json::Value>> {
+    let repo = MediaRepository::new(state.pool);
+    let media = repo.find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Media with id {} not found", id)))?;
+
+    // Remove the file first, so a failure leaves the row in place and the delete can be retried
+    state
+        .store
+        .delete(&media.storage_key)
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to remove media file: {}", e)))?;
+
+    repo.delete(id).await?;
+
+    Ok(Json(serde_json::json!({ "message": "Media deleted successfully" })))
+}
+
+async fn serve_media(
+    State(state): State<MediaState>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> Result<Response> {
+    let repo = MediaRepository::new(state.pool);
+    let media = repo.find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Media with id {} not found", id)))?;
+
+    let size = media.size_bytes as u64;
+    let requested = headers
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| parse_range(v, size));
+
+    let (status, range) = match requested {
+        Some(RangeRequest::Satisfiable(range)) => (StatusCode::PARTIAL_CONTENT, range),
+        Some(RangeRequest::Unsatisfiable) => {
+            let content_range = format!("bytes */{}", size);
+            return Ok((StatusCode::RANGE_NOT_SATISFIABLE, [(header::CONTENT_RANGE, content_range)]).into_response());
+        }
+        Some(RangeRequest::Ignored) | None => (StatusCode::OK, 0..size),
+    };
+
+    let body = state
+        .store
+        .get(&media.storage_key, range.clone())
+        .await
+        .map_err(|e| AppError::InternalError(format!("Failed to read media file: {}", e)))?;
+
+    let mut response = (status, LastModified(media.created_at), body).into_response();
+    let response_headers = response.headers_mut();
+    response_headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+    response_headers.insert(header::CONTENT_LENGTH, HeaderValue::from(range.end - range.start));
+    if let Ok(content_type) = HeaderValue::from_str(&media.content_type) {
+        response_headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    // Media ids are never reused and files are never modified, so the id is a strong validator
+    if let Ok(etag) = HeaderValue::from_str(&format!("\"{}\"", media.id)) {
+        response_headers.insert(header::ETAG, etag);
+    }
+    if status == StatusCode::PARTIAL_CONTENT {
+        let content_range = format!("bytes {}-{}/{}", range.start, range.end - 1, size);
+        if let Ok(content_range) = HeaderValue::from_str(&content_range) {
+            response_headers.insert(header::CONTENT_RANGE, content_range);
+        }
+    }
+
+    Ok(response)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RangeRequest {
+    Satisfiable(Range<u64>),
+    Unsatisfiable,
+    // Malformed or multi-range requests are answered with the whole file
+    Ignored,
+}
+
+// Parses a single-range `Range: bytes=...` header (RFC 9110 section 14.1.2)
+fn parse_range(header: &str, size: u64) -> RangeRequest {
+    let Some(spec) = header.trim().strip_prefix("bytes=") else {
+        return RangeRequest::Ignored;
+    };
+    if spec.contains(',') {
+        return RangeRequest::Ignored;
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return RangeRequest::Ignored;
+    };
+
+    let range = match (start.trim(), end.trim()) {
+        ("", "") => return RangeRequest::Ignored,
+        // Suffix range: the last N bytes
+        ("", suffix) => match suffix.parse::<u64>() {
+            Ok(0) => return RangeRequest::Unsatisfiable,
+            Ok(n) => size.saturating_sub(n)..size,
+            Err(_) => return RangeRequest::Ignored,
+        },
+        (start, "") => match start.parse::<u64>() {
+            Ok(start) => start..size,
+            Err(_) => return RangeRequest::Ignored,
+        },
+        (start, end) => match (start.parse::<u64>(), end.parse::<u64>()) {
+            (Ok(start), Ok(end)) if start <= end => start..end.saturating_add(1).min(size),
+            _ => return RangeRequest::Ignored,
+        },
+    };
+
+    if range.is_empty() || range.start >= size {
+        RangeRequest::Unsatisfiable
+    } else {
+        RangeRequest::Satisfiable(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_range, RangeRequest};
+
+    #[test]
+    fn parses_bounded_ranges() {
+        assert_eq!(parse_range("bytes=0-9", 100), RangeRequest::Satisfiable(0..10));
+        assert_eq!(parse_range("bytes=90-200", 100), RangeRequest::Satisfiable(90..100));
+        assert_eq!(parse_range(" bytes= 5 - 5 ", 100), RangeRequest::Satisfiable(5..6));
+    }
+
+    #[test]
+    fn parses_suffix_ranges() {
+        assert_eq!(parse_range("bytes=-10", 100), RangeRequest::Satisfiable(90..100));
+        assert_eq!(parse_range("bytes=-500", 100), RangeRequest::Satisfiable(0..100));
+        assert_eq!(parse_range("bytes=-0", 100), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=-10", 0), RangeRequest::Unsatisfiable);
+    }
+
+    #[test]
+    fn parses_open_ended_ranges() {
+        assert_eq!(parse_range("bytes=0-", 100), RangeRequest::Satisfiable(0..100));
+        assert_eq!(parse_range("bytes=99-", 100), RangeRequest::Satisfiable(99..100));
+        assert_eq!(parse_range("bytes=100-", 100), RangeRequest::Unsatisfiable);
+    }
+
+    #[test]
+    fn clamps_overflowing_ends() {
+        assert_eq!(parse_range("bytes=0-18446744073709551615", 100), RangeRequest::Satisfiable(0..100));
+        assert_eq!(parse_range("bytes=18446744073709551615-18446744073709551615", 100), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-18446744073709551616", 100), RangeRequest::Ignored);
+    }
+
+    #[test]
+    fn rejects_out_of_bounds_ranges() {
+        assert_eq!(parse_range("bytes=100-200", 100), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-0", 0), RangeRequest::Unsatisfiable);
+        assert_eq!(parse_range("bytes=0-", 0), RangeRequest::Unsatisfiable);
+    }
+
+    #[test]
+    fn ignores_malformed_and_multiple_ranges() {
+        for header in ["items=0-9", "bytes=", "bytes=-", "bytes=5", "bytes=9-0", "bytes=a-b", "bytes=0-1,5-6"] {
+            assert_eq!(parse_range(header, 100), RangeRequest::Ignored, "{}", header);
+        }
+    }
+}
\ No newline at end of file
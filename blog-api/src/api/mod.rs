@@ -1,24 +1,137 @@
+pub mod auth;
+pub mod events;
+pub mod feeds;
+pub mod follows;
+pub mod graphql;
+pub mod health;
+pub mod images;
+pub mod media;
+pub mod notifications;
+pub mod openapi;
+pub mod pages;
 pub mod posts;
+pub mod reading_lists;
+pub mod sitemap;
 pub mod users;
+pub mod v2;
+pub mod versioning;
+pub mod webhooks;
 
 use axum::{routing::get, Json, Router};
 use serde_json::json;
 use sqlx::PgPool;
 use std::path::PathBuf;
+use std::sync::Arc;
+use tower::ServiceBuilder;
 use tower_http::services::ServeDir;
 
-pub fn create_router(pool: PgPool) -> Router {
+use crate::config::AppConfig;
+use crate::events::Events;
+use crate::images::ImagePipeline;
+use crate::middleware::{HttpCacheLayer, InMemoryStore, RateLimiter};
+use crate::notifications::Notifier;
+use crate::storage::MediaStore;
+use crate::telemetry::metrics::HttpMetricsLayer;
+use crate::webhooks::Webhooks;
+
+// State shared by the routers of every API version, built once so both versions use the
+// same caches and connections
+#[derive(Clone)]
+struct Services {
+    posts: posts::PostState,
+    users: users::UserState,
+    limiter: RateLimiter,
+}
+
+pub fn create_router(
+    pool: PgPool,
+    config: &AppConfig,
+    media_store: Arc<dyn MediaStore>,
+    image_pipeline: ImagePipeline,
+    notifier: Notifier,
+    webhooks: Webhooks,
+    events: Events,
+) -> Router {
+    let post_hooks = posts::PostHooks {
+        notifier: notifier.clone(),
+        webhooks: webhooks.clone(),
+        events: events.clone(),
+    };
+    
+    let limiter = RateLimiter::new(&config.rate_limit, Arc::new(InMemoryStore::new()));
+    let schema = crate::graphql::build_schema(
+        pool.clone(),
+        &config.graphql,
+        post_hooks.clone(),
+        webhooks.clone(),
+        media_store.clone(),
+        &limiter,
+    );
+    
+    let services = Services {
+        posts: posts::PostState::new(pool.clone(), config, post_hooks),
+        users: users::UserState::new(pool.clone(), media_store.clone(), webhooks.clone()),
+        limiter,
+    };
+    // Routes that respond the same in every version so far
+    let user_routes =
+        reading_lists::create_router(pool.clone(), config).merge(follows::create_router(pool.clone(), notifier));
+    let unchanged = Router::new()
+        .nest("/notifications", notifications::create_router(pool.clone()))
+        .nest("/webhooks", webhooks::create_router(pool.clone(), webhooks))
+        .nest("/feed", follows::create_feed_router(pool.clone(), config))
+        .nest("/media", media::create_router(pool.clone(), media_store.clone(), config))
+        .nest("/images", images::create_router(pool.clone(), media_store.clone(), image_pipeline.clone(), config));
+
+    let api_limit = services.limiter.layer("api");
+    // Metrics are also recorded inside v1 and v2, since the unversioned /api paths are
+    // only matched there
+    let v1 = v1_routes(&services, config, user_routes.clone(), unchanged.clone());
+    let v1 = versioning::deprecate(v1, &config.api_versions)
+        .layer(api_limit.clone())
+        .layer(HttpMetricsLayer);
+    let v2 = v2_routes(&services, config, user_routes, unchanged)
+        .layer(api_limit.clone())
+        .layer(HttpMetricsLayer);
+    
     // Create a router for API endpoints
     let api_router = Router::new()
-        .route("/", get(root_handler))
-        .nest("/api/posts", posts::create_router(pool.clone()))
-        .nest("/api/users", users::create_router(pool.clone()))
+        .nest("/api/v1", v1.clone())
+        .nest("/api/v2", v2.clone())
+        .nest_service("/api", versioning::dispatch(v1, v2))
+        .nest("/graphql", graphql::create_router(schema, config).layer(api_limit))
+        .nest("/api/events", events::create_router(pool.clone(), events, config))
+        .nest("/media", media::create_file_router(pool.clone(), media_store.clone(), config))
+        .nest("/images", images::create_file_router(pool.clone(), media_store, image_pipeline, config))
+        .nest("/feeds", feeds::create_router(pool.clone(), config))
+        .merge(openapi::create_router())
+        .merge(sitemap::create_router(pool.clone(), config))
+        .merge(pages::create_router(pool.clone(), config))
         .route("/health", get(health_check));
     
     // Serve static files from the public directory
     let public_path = PathBuf::from("public");
+    let static_files = ServiceBuilder::new()
+        .layer(HttpCacheLayer::new(&config.http_cache.static_files, &config.http_cache.drafts))
+        .service(ServeDir::new(public_path));
     
-    api_router.nest_service("/ui", ServeDir::new(public_path))
+    api_router.nest_service("/ui", static_files)
+}
+
+// Version 1, also served on the unversioned /api paths
+fn v1_routes(s: &Services, config: &AppConfig, user_routes: Router, unchanged: Router) -> Router {
+    Router::new()
+        .nest("/posts", posts::create_router(s.posts.clone(), config, &s.limiter))
+        .nest("/users", users::create_router(s.users.clone(), &s.limiter).merge(user_routes))
+        .merge(unchanged)
+}
+
+// Version 2 wraps listings in a pagination envelope and embeds authors in posts
+fn v2_routes(s: &Services, config: &AppConfig, user_routes: Router, unchanged: Router) -> Router {
+    Router::new()
+        .nest("/posts", v2::posts::create_router(s.posts.clone(), config, &s.limiter))
+        .nest("/users", v2::users::create_router(s.users.clone(), &s.limiter).merge(user_routes))
+        .merge(unchanged)
 }
 
 async fn health_check() -> &'static str {
@@ -31,8 +144,26 @@ async fn root_handler() -> Json<serde_json::Value> {
         "version": "0.1.0",
         "endpoints": {
             "health": "/health",
+            "health_live": "/health/live",
+            "health_ready": "/health/ready",
+            "v2": "/api/v2",
             "users": "/api/users",
             "posts": "/api/posts",
+            "feed": "/api/feed",
+            "events": "/api/events",
+            "graphql": "/graphql",
+            "notifications": "/api/notifications",
+            "webhooks": "/api/webhooks",
+            "media": "/api/media",
+            "images": "/api/images",
+            "feeds": {
+                "rss": "/feeds/rss.xml",
+                "atom": "/feeds/atom.xml",
+                "json": "/feeds/feed.json"
+            },
+            "sitemap": "/sitemap.xml",
+            "openapi": "/api/openapi.json",
+            "docs": "/api/docs",
             "ui": "/ui"
         },
         "documentation": "See README.md for API documentation"
@@ -0,0 +1,187 @@
+use std::collections::{BTreeMap, HashMap};
+
+use axum::{
+    extract::{Path, Query, State},
+    routing::{get, post, put},
+    Json, Router,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::auth::CurrentUser;
+use crate::db::{NotificationRepository, PostRepository, UserRepository};
+use crate::errors::{AppError, Result};
+use crate::models::notification::{
+    Notification, NotificationActor, NotificationListResponse, NotificationResponse, KINDS, KIND_FOLLOW,
+    KIND_MENTION, KIND_REACTION,
+};
+use crate::models::read::MarkReadResponse;
+
+// Number of actors named in a notification before the rest are summed up
+const NAMED_ACTORS: usize = 3;
+
+pub fn create_router(pool: PgPool) -> Router {
+    Router::new()
+        .route("/", get(list_notifications))
+        .route("/read", post(mark_all_read))
+        .route("/:id/read", put(mark_read))
+        .route("/preferences", get(get_preferences).put(update_preferences))
+        .with_state(pool)
+}
+
+#[derive(Debug, Deserialize)]
+struct NotificationParams {
+    #[serde(default = "default_limit")]
+    limit: i64,
+    #[serde(default)]
+    offset: i64,
+    #[serde(default)]
+    unread_only: bool,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+async fn list_notifications(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Query(params): Query<NotificationParams>,
+) -> Result<Json<NotificationListResponse>> {
+    let repo = NotificationRepository::new(pool.clone());
+    let notifications = repo.list(user.0, params.unread_only, params.limit, params.offset).await?;
+    let unread_count = repo.count_unread(user.0).await?;
+    
+    // Look up everyone and everything the page refers to in one go
+    let mut actor_ids: Vec<Uuid> = notifications
+        .iter()
+        .flat_map(|n| n.actor_ids.iter().take(NAMED_ACTORS).copied())
+        .collect();
+    actor_ids.sort_unstable();
+    actor_ids.dedup();
+    let usernames: HashMap<Uuid, String> = UserRepository::new(pool.clone())
+        .find_by_ids(&actor_ids)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u.username))
+        .collect();
+    
+    let post_ids: Vec<Uuid> = notifications.iter().filter_map(|n| n.post_id).collect();
+    let titles: HashMap<Uuid, String> = PostRepository::new(pool)
+        .find_by_ids(&post_ids)
+        .await?
+        .into_iter()
+        .map(|p| (p.id, p.title))
+        .collect();
+    
+    let notifications = notifications
+        .into_iter()
+        .map(|n| to_response(n, &usernames, &titles))
+        .collect();
+    
+    Ok(Json(NotificationListResponse { unread_count, notifications }))
+}
+
+async fn mark_read(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    let repo = NotificationRepository::new(pool);
+    if !repo.mark_read(user.0, id).await? {
+        return Err(AppError::NotFoundError(format!("Notification with id {} not found", id)));
+    }
+    
+    let unread_count = repo.count_unread(user.0).await?;
+    Ok(Json(serde_json::json!({ "id": id, "read": true, "unread_count": unread_count })))
+}
+
+async fn mark_all_read(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+) -> Result<Json<MarkReadResponse>> {
+    let marked = NotificationRepository::new(pool).mark_all_read(user.0).await?;
+    Ok(Json(MarkReadResponse { marked }))
+}
+
+// Preferences map each notification kind to whether it is enabled
+async fn get_preferences(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+) -> Result<Json<BTreeMap<String, bool>>> {
+    let muted = NotificationRepository::new(pool).muted_kinds(user.0).await?;
+    Ok(Json(preferences(&muted)))
+}
+
+// Only the kinds present in the body are changed
+async fn update_preferences(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Json(payload): Json<BTreeMap<String, bool>>,
+) -> Result<Json<BTreeMap<String, bool>>> {
+    if let Some(kind) = payload.keys().find(|k| !KINDS.contains(&k.as_str())) {
+        return Err(AppError::BadRequest(format!("Unknown notification kind: {}", kind)));
+    }
+    
+    let repo = NotificationRepository::new(pool);
+    for (kind, enabled) in &payload {
+        repo.set_muted(user.0, kind, !enabled).await?;
+    }
+    
+    let muted = repo.muted_kinds(user.0).await?;
+    Ok(Json(preferences(&muted)))
+}
+
+fn preferences(muted: &[String]) -> BTreeMap<String, bool> {
+    KINDS
+        .iter()
+        .map(|kind| (kind.to_string(), !muted.iter().any(|m| m == kind)))
+        .collect()
+}
+
+fn to_response(
+    notification: Notification,
+    usernames: &HashMap<Uuid, String>,
+    titles: &HashMap<Uuid, String>,
+) -> NotificationResponse {
+    let actors: Vec<NotificationActor> = notification
+        .actor_ids
+        .iter()
+        .take(NAMED_ACTORS)
+        .filter_map(|id| usernames.get(id).map(|username| NotificationActor { id: *id, username: username.clone() }))
+        .collect();
+    let actor_count = notification.actor_ids.len();
+    let title = notification.post_id.and_then(|id| titles.get(&id)).map(String::as_str).unwrap_or("a post");
+    
+    let who = describe_actors(&actors, actor_count);
+    let message = match notification.kind.as_str() {
+        KIND_FOLLOW => format!("{} followed you", who),
+        KIND_REACTION => format!("{} reacted to your post \"{}\"", who, title),
+        KIND_MENTION => format!("{} mentioned you in \"{}\"", who, title),
+        _ => who,
+    };
+    
+    NotificationResponse {
+        id: notification.id,
+        kind: notification.kind,
+        post_id: notification.post_id,
+        actors,
+        actor_count,
+        message,
+        read: notification.read_at.is_some(),
+        created_at: notification.created_at,
+        updated_at: notification.updated_at,
+    }
+}
+
+// "alice", "alice and bob", or "alice and 4 others"
+fn describe_actors(actors: &[NotificationActor], total: usize) -> String {
+    let first = actors.first().map(|a| a.username.as_str()).unwrap_or("Someone");
+    match total {
+        0 | 1 => first.to_string(),
+        2 if actors.len() == 2 => format!("{} and {}", first, actors[1].username),
+        2 => format!("{} and 1 other", first),
+        _ => format!("{} and {} others", first, total - 1),
+    }
+}
\ No newline at end of file
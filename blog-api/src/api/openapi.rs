@@ -0,0 +1,184 @@
+use axum::Router;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use utoipa_swagger_ui::SwaggerUi;
+
+use super::{posts, users};
+
+// The OpenAPI document is generated from the `#[utoipa::path]` attributes on the handlers and
+// the `ToSchema` derives on the models, so it changes along with them
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Blog API"),
+    nest(
+        (path = "/api/posts", api = posts::PostsApi, tags = ["posts"]),
+        (path = "/api/users", api = users::UsersApi, tags = ["users"]),
+    ),
+    modifiers(&UserIdHeader),
+)]
+struct ApiDoc;
+
+// The X-User-Id header that identifies the user, see `api::auth`
+struct UserIdHeader;
+
+impl Modify for UserIdHeader {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme("user_id", SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-User-Id"))));
+    }
+}
+
+// Serves the document at /api/openapi.json and Swagger UI at /api/docs
+pub fn create_router() -> Router {
+    let mut doc = ApiDoc::openapi();
+    // The crate doesn't declare a license, which would otherwise show up as an empty one
+    doc.info.license = None;
+
+    SwaggerUi::new("/api/docs").url("/api/openapi.json", doc).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use serde::de::DeserializeOwned;
+    use serde_json::{json, Value};
+    use validator::Validate;
+
+    use super::ApiDoc;
+    use crate::models::post::{CreatePostRequest, UpdatePostRequest};
+    use crate::models::read::MarkReadRequest;
+    use crate::models::user::{CreateUserRequest, UpdateUserRequest};
+    use utoipa::OpenApi;
+
+    fn spec() -> Value {
+        serde_json::to_value(ApiDoc::openapi()).unwrap()
+    }
+
+    // (method, path) of every `.route(...)` in a router's source, written as OpenAPI paths under `base`
+    fn declared_routes(source: &str, base: &str) -> BTreeSet<(String, String)> {
+        let mut routes = BTreeSet::new();
+        for line in source.lines() {
+            let Some(rest) = line.trim().strip_prefix(".route(\"") else {
+                continue;
+            };
+            let (path, handlers) = rest.split_once('"').unwrap();
+            let path: Vec<String> = path
+                .trim_end_matches('/')
+                .split('/')
+                .map(|segment| match segment.strip_prefix(':') {
+                    Some(param) => format!("{{{}}}", param),
+                    None => segment.to_string(),
+                })
+                .collect();
+
+            for method in ["get", "post", "put", "patch", "delete"] {
+                let call = format!("{}(", method);
+                let routed = handlers
+                    .match_indices(&call)
+                    .any(|(i, _)| !handlers[..i].ends_with(|c: char| c.is_ascii_alphanumeric() || c == '_'));
+                if routed {
+                    routes.insert((method.to_string(), format!("{}{}", base, path.join("/"))));
+                }
+            }
+        }
+        routes
+    }
+
+    #[test]
+    fn documents_every_posts_and_users_route() {
+        let mut declared = declared_routes(include_str!("posts.rs"), "/api/posts");
+        declared.extend(declared_routes(include_str!("users.rs"), "/api/users"));
+
+        let documented: BTreeSet<(String, String)> = spec()["paths"]
+            .as_object()
+            .unwrap()
+            .iter()
+            .flat_map(|(path, item)| item.as_object().unwrap().keys().map(move |method| (method.clone(), path.clone())))
+            .collect();
+
+        assert_eq!(declared, documented);
+    }
+
+    fn rejects<T: DeserializeOwned + Validate>(body: &Value, field: &str) -> bool {
+        let request: T = serde_json::from_value(body.clone()).expect("test body should deserialize");
+        request.validate().is_err_and(|e| e.field_errors().contains_key(field))
+    }
+
+    fn is_type(schema: &Value, name: &str) -> bool {
+        schema["type"] == name || schema["type"].as_array().is_some_and(|types| types.iter().any(|t| t == name))
+    }
+
+    // Validates values just inside and just outside every limit the spec documents for `schema`,
+    // and checks that fields documented without limits accept any length
+    fn check_limits<T: DeserializeOwned + Validate>(schema: &str, valid: Value) {
+        let spec = spec();
+        let properties = spec["components"]["schemas"][schema]["properties"]
+            .as_object()
+            .unwrap_or_else(|| panic!("{} is not in the spec", schema));
+        assert!(serde_json::from_value::<T>(valid.clone()).unwrap().validate().is_ok(), "{} example is invalid", schema);
+
+        let with = |field: &str, value: Value| {
+            let mut body = valid.clone();
+            body[field] = value;
+            body
+        };
+        let string = |len: u64| json!("x".repeat(len as usize));
+        let items = |len: u64| json!(vec![uuid::Uuid::nil(); len as usize]);
+
+        for (field, property) in properties {
+            let context = format!("{}.{}", schema, field);
+            if is_type(property, "string") {
+                match property["minLength"].as_u64() {
+                    Some(min) => {
+                        assert!(rejects::<T>(&with(field, string(min - 1)), field), "{} allows shorter values", context);
+                        assert!(!rejects::<T>(&with(field, string(min)), field), "{} rejects its minLength", context);
+                    }
+                    None if property.get("format").is_none() => {
+                        assert!(!rejects::<T>(&with(field, string(0)), field), "{} has an undocumented minimum", context);
+                    }
+                    None => {}
+                }
+                match property["maxLength"].as_u64() {
+                    Some(max) => {
+                        assert!(!rejects::<T>(&with(field, string(max)), field), "{} rejects its maxLength", context);
+                        assert!(rejects::<T>(&with(field, string(max + 1)), field), "{} allows longer values", context);
+                    }
+                    None if property.get("format").is_none() => {
+                        assert!(!rejects::<T>(&with(field, string(10_000)), field), "{} has an undocumented maximum", context);
+                    }
+                    None => {}
+                }
+            } else if is_type(property, "array") {
+                match property["maxItems"].as_u64() {
+                    Some(max) => {
+                        assert!(!rejects::<T>(&with(field, items(max)), field), "{} rejects its maxItems", context);
+                        assert!(rejects::<T>(&with(field, items(max + 1)), field), "{} allows more items", context);
+                    }
+                    None => {
+                        assert!(!rejects::<T>(&with(field, items(10_000)), field), "{} has an undocumented maximum", context);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn post_limits_match_validation() {
+        let post = json!({ "title": "A title", "content": "Some content here" });
+        check_limits::<CreatePostRequest>("CreatePostRequest", post.clone());
+        check_limits::<UpdatePostRequest>("UpdatePostRequest", post);
+    }
+
+    #[test]
+    fn user_limits_match_validation() {
+        let user = json!({ "username": "reader", "email": "reader@example.com", "password": "correct horse" });
+        check_limits::<CreateUserRequest>("CreateUserRequest", user.clone());
+        check_limits::<UpdateUserRequest>("UpdateUserRequest", user);
+    }
+
+    #[test]
+    fn mark_read_limits_match_validation() {
+        check_limits::<MarkReadRequest>("MarkReadRequest", json!({ "post_ids": [] }));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use askama::Template;
+use axum::{
+    extract::{Path, Query, State},
+    handler::Handler,
+    http::{header, HeaderMap},
+    response::{Html, IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::SecondsFormat;
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::db::{PostRepository, UserRepository};
+use crate::errors::{AppError, Result};
+use crate::middleware::http_cache::LastModified;
+use crate::middleware::HttpCacheLayer;
+use crate::models::post::Post;
+
+const PAGE_SIZE: i64 = 10;
+const EXCERPT_LENGTH: usize = 300;
+const DESCRIPTION_LENGTH: usize = 160;
+
+#[derive(Clone)]
+struct PageState {
+    pool: PgPool,
+    site: Arc<SiteInfo>,
+}
+
+struct SiteInfo {
+    title: String,
+    description: String,
+    base_url: String,
+}
+
+pub fn create_router(pool: PgPool, config: &AppConfig) -> Router {
+    let state = PageState {
+        pool,
+        site: Arc::new(SiteInfo {
+            title: config.site.title.clone(),
+            description: config.site.description.clone(),
+            base_url: config.site.base_url().to_string(),
+        }),
+    };
+    let list_cache = HttpCacheLayer::new(&config.http_cache.lists, &config.http_cache.drafts);
+    let post_cache = HttpCacheLayer::new(&config.http_cache.posts, &config.http_cache.drafts);
+
+    Router::new()
+        .route("/", get(home.layer(list_cache.clone())))
+        .route("/posts/:id", get(post_page.layer(post_cache)))
+        .route("/authors/:id", get(author_page.layer(list_cache)))
+        .with_state(state)
+}
+
+// Meta tags shared by every page, rendered by base.html
+struct PageMeta {
+    site_name: String,
+    base_url: String,
+    title: String,
+    description: String,
+    canonical_url: String,
+    og_type: &'static str,
+    image_url: Option<String>,
+}
+
+struct PostView {
+    id: Uuid,
+    title: String,
+    content: String,
+    excerpt: String,
+    author_id: Uuid,
+    author_name: String,
+    published_date: String,
+    published_iso: String,
+}
+
+impl PostView {
+    fn new(post: Post, author_name: String) -> Self {
+        Self {
+            id: post.id,
+            excerpt: truncate(&post.content, EXCERPT_LENGTH),
+            title: post.title,
+            content: post.content,
+            author_id: post.author_id,
+            author_name,
+            published_date: post.created_at.format("%B %-d, %Y").to_string(),
+            published_iso: post.created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "home.html")]
+struct HomeTemplate {
+    meta: PageMeta,
+    posts: Vec<PostView>,
+    prev_page: Option<String>,
+    next_page: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "author.html")]
+struct AuthorTemplate {
+    meta: PageMeta,
+    author_id: Uuid,
+    author_name: String,
+    posts: Vec<PostView>,
+    prev_page: Option<String>,
+    next_page: Option<String>,
+}
+
+#[derive(Template)]
+#[template(path = "post.html")]
+struct PostTemplate {
+    meta: PageMeta,
+    post: PostView,
+}
+
+#[derive(Debug, Deserialize)]
+struct PageQuery {
+    #[serde(default = "default_page")]
+    page: i64,
+}
+
+fn default_page() -> i64 {
+    1
+}
+
+// `/` keeps serving the JSON API index to API clients; browsers asking for
+// HTML get the rendered home page instead
+async fn home(
+    State(state): State<PageState>,
+    headers: HeaderMap,
+    Query(query): Query<PageQuery>,
+) -> Result<Response> {
+    let vary = [(header::VARY, "Accept")];
+    if !accepts_html(&headers) {
+        return Ok((vary, super::root_handler().await).into_response());
+    }
+
+    let page = query.page.max(1);
+    let repo = PostRepository::new(state.pool.clone());
+    let mut posts = repo.list(PAGE_SIZE + 1, (page - 1) * PAGE_SIZE, true).await?;
+    let has_next = posts.len() as i64 > PAGE_SIZE;
+    posts.truncate(PAGE_SIZE as usize);
+
+    let site = &state.site;
+    let last_modified = posts.iter().map(|p| p.updated_at).max().map(LastModified);
+    let template = HomeTemplate {
+        meta: PageMeta {
+            site_name: site.title.clone(),
+            base_url: site.base_url.clone(),
+            title: if page > 1 { format!("{} - Page {}", site.title, page) } else { site.title.clone() },
+            description: site.description.clone(),
+            canonical_url: page_url(&format!("{}/", site.base_url), page),
+            og_type: "website",
+            image_url: None,
+        },
+        posts: with_authors(&state.pool, posts).await?,
+        prev_page: (page > 1).then(|| page_url("/", page - 1)),
+        next_page: has_next.then(|| page_url("/", page + 1)),
+    };
+
+    Ok((vary, last_modified, render(&template)?).into_response())
+}
+
+async fn post_page(
+    State(state): State<PageState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let not_found = || AppError::NotFoundError(format!("Post with id {} not found", id));
+
+    // Drafts are only reachable through the JSON API
+    let post = PostRepository::new(state.pool.clone())
+        .find_by_id(id)
+        .await?
+        .filter(|p| p.published)
+        .ok_or_else(not_found)?;
+    let author = UserRepository::new(state.pool.clone())
+        .find_by_id(post.author_id)
+        .await?
+        .ok_or_else(not_found)?;
+
+    let site = &state.site;
+    let last_modified = LastModified(post.updated_at);
+    let template = PostTemplate {
+        meta: PageMeta {
+            site_name: site.title.clone(),
+            base_url: site.base_url.clone(),
+            title: post.title.clone(),
+            description: truncate(&post.content, DESCRIPTION_LENGTH),
+            canonical_url: format!("{}/posts/{}", site.base_url, post.id),
+            og_type: "article",
+            image_url: Some(format!("{}/api/posts/{}/og-image.png", site.base_url, post.id)),
+        },
+        post: PostView::new(post, author.username),
+    };
+
+    Ok((last_modified, render(&template)?))
+}
+
+async fn author_page(
+    State(state): State<PageState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<PageQuery>,
+) -> Result<impl IntoResponse> {
+    let author = UserRepository::new(state.pool.clone())
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFoundError(format!("User with id {} not found", id)))?;
+
+    let page = query.page.max(1);
+    let repo = PostRepository::new(state.pool.clone());
+    let mut posts = repo.find_by_author(id, PAGE_SIZE + 1, (page - 1) * PAGE_SIZE, true).await?;
+    let has_next = posts.len() as i64 > PAGE_SIZE;
+    posts.truncate(PAGE_SIZE as usize);
+
+    let site = &state.site;
+    let path = format!("/authors/{}", id);
+    let last_modified = posts.iter().map(|p| p.updated_at).max().map(LastModified);
+    let template = AuthorTemplate {
+        meta: PageMeta {
+            site_name: site.title.clone(),
+            base_url: site.base_url.clone(),
+            title: format!("Posts by {} - {}", author.username, site.title),
+            description: format!("Posts by {} on {}", author.username, site.title),
+            canonical_url: page_url(&format!("{}{}", site.base_url, path), page),
+            og_type: "profile",
+            image_url: None,
+        },
+        author_id: author.id,
+        posts: posts
+            .into_iter()
+            .map(|post| PostView::new(post, author.username.clone()))
+            .collect(),
+        author_name: author.username,
+        prev_page: (page > 1).then(|| page_url(&path, page - 1)),
+        next_page: has_next.then(|| page_url(&path, page + 1)),
+    };
+
+    Ok((last_modified, render(&template)?))
+}
+
+async fn with_authors(pool: &PgPool, posts: Vec<Post>) -> Result<Vec<PostView>> {
+    let mut author_ids: Vec<Uuid> = posts.iter().map(|p| p.author_id).collect();
+    author_ids.sort_unstable();
+    author_ids.dedup();
+    let authors: HashMap<Uuid, String> = UserRepository::new(pool.clone())
+        .find_by_ids(&author_ids)
+        .await?
+        .into_iter()
+        .map(|u| (u.id, u.username))
+        .collect();
+
+    Ok(posts
+        .into_iter()
+        .map(|post| {
+            let author_name = authors.get(&post.author_id).cloned().unwrap_or_default();
+            PostView::new(post, author_name)
+        })
+        .collect())
+}
+
+fn render<T: Template>(template: &T) -> Result<Html<String>> {
+    template
+        .render()
+        .map(Html)
+        .map_err(|e| AppError::InternalError(format!("Failed to render template: {}", e)))
+}
+
+fn accepts_html(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains("text/html"))
+}
+
+fn page_url(path: &str, page: i64) -> String {
+    if page > 1 {
+        format!("{}?page={}", path, page)
+    } else {
+        path.to_string()
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", text[..end].trim_end()),
+        None => text.to_string(),
+    }
+}
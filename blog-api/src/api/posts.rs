@@ -1,140 +1,430 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use axum::{
-    extract::{Path, Query, State},
-    routing::get,
+    extract::{FromRef, Path, Query, State},
+    handler::Handler,
+    http::header,
+    response::IntoResponse,
+    routing::{self, get, post, put},
     Json, Router,
 };
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use sqlx::PgPool;
+use utoipa::{IntoParams, OpenApi};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::db::{PostRepository, UserRepository};
-use crate::errors::{AppError, Result};
-use crate::models::post::{CreatePostRequest, PostResponse, UpdatePostRequest};
+use super::auth::{CurrentUser, MaybeUser};
+use crate::config::{AppConfig, ReactionsConfig};
+use crate::db::{PostRepository, ReactionRepository, ReadRepository, UserRepository};
+use crate::errors::{AppError, ErrorResponse, Result};
+use crate::events::Events;
+use crate::middleware::http_cache::{LastModified, Visibility};
+use crate::middleware::{HttpCacheLayer, RateLimiter};
+use crate::models::post::{CreatePostRequest, Post, PostResponse, UpdatePostRequest};
+use crate::models::reaction::{ReactionSummary, ReactionsResponse};
+use crate::models::read::{MarkReadRequest, MarkReadResponse, ReadStatusResponse};
+use crate::notifications::{self, Event, Notifier};
+use crate::models::webhook::{EVENT_POST_CREATED, EVENT_POST_DELETED, EVENT_POST_PUBLISHED, EVENT_POST_UPDATED};
+use crate::og_image::{Card, OgImages};
+use crate::webhooks::Webhooks;
+
+#[derive(Clone, FromRef)]
+pub struct PostState {
+    pool: PgPool,
+    og_images: Arc<OgImages>,
+    reactions: Arc<ReactionsConfig>,
+    hooks: PostHooks,
+}
+
+impl PostState {
+    pub(super) fn new(pool: PgPool, config: &AppConfig, hooks: PostHooks) -> Self {
+        Self {
+            pool,
+            og_images: Arc::new(OgImages::new(&config.og_image, &config.site.title)),
+            reactions: Arc::new(config.reactions.clone()),
+            hooks,
+        }
+    }
+}
+
+// Responses differ per user once reactions are personalised
+pub(super) const VARY_USER: [(header::HeaderName, &str); 1] = [(header::VARY, "X-User-Id")];
+
+pub fn create_router(state: PostState, config: &AppConfig, limiter: &RateLimiter) -> Router {
+    let (list_cache, post_cache) = cache_layers(config);
+    let writes = limiter.layer("posts");
 
-pub fn create_router(pool: PgPool) -> Router {
     Router::new()
-        .route("/", get(list_posts).post(create_post))
-        .route("/:id", get(get_post).put(update_post).delete(delete_post))
-        .route("/user/:user_id", get(list_posts_by_user))
-        .with_state(pool)
+        .route("/", get(list_posts.layer(list_cache.clone())).post(create_post.layer(writes.clone())))
+        .route("/:id", get(get_post.layer(post_cache)).put(update_post.layer(writes)))
+        .route("/user/:user_id", get(list_posts_by_user.layer(list_cache)))
+        .merge(common_routes(config))
+        .with_state(state)
 }
 
-#[derive(Debug, Deserialize)]
-struct Pagination {
+// Routes that respond the same in every API version
+pub(super) fn common_routes(config: &AppConfig) -> Router<PostState> {
+    let (_, post_cache) = cache_layers(config);
+
+    Router::new()
+        .route("/:id", routing::delete(delete_post))
+        .route("/:id/og-image.png", get(get_og_image.layer(post_cache)))
+        .route("/:id/reactions/:kind", put(add_reaction).delete(remove_reaction))
+        .route("/:id/read", put(mark_read).delete(mark_unread))
+        .route("/read", post(mark_many_read))
+}
+
+// Cache layers for listings and for single posts
+pub(super) fn cache_layers(config: &AppConfig) -> (HttpCacheLayer, HttpCacheLayer) {
+    let cache = &config.http_cache;
+    (HttpCacheLayer::new(&cache.lists, &cache.drafts), HttpCacheLayer::new(&cache.posts, &cache.drafts))
+}
+
+// OpenAPI description of the routes above, served by `api::openapi`
+#[derive(OpenApi)]
+#[openapi(paths(
+    list_posts,
+    create_post,
+    get_post,
+    update_post,
+    delete_post,
+    get_og_image,
+    add_reaction,
+    remove_reaction,
+    mark_read,
+    mark_unread,
+    mark_many_read,
+    list_posts_by_user,
+))]
+pub(super) struct PostsApi;
+
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct Pagination {
     #[serde(default = "default_limit")]
-    limit: i64,
+    #[param(default = 10)]
+    pub(super) limit: i64,
+    #[serde(default)]
+    pub(super) offset: i64,
+    // Ignored by `list_posts_by_user`, which lists drafts as well
     #[serde(default)]
-    offset: i64,
+    pub(super) published_only: bool,
+    // Only honoured by `list_posts`, and requires an X-User-Id header
     #[serde(default)]
-    published_only: bool,
+    pub(super) unread_only: bool,
 }
 
 fn default_limit() -> i64 {
     10
 }
 
+#[utoipa::path(
+    get,
+    path = "",
+    params(Pagination),
+    security((), ("user_id" = [])),
+    responses(
+        (status = 200, description = "Posts, newest first", body = [PostResponse]),
+        (status = 401, description = "`unread_only` without a user", body = ErrorResponse),
+    ),
+)]
 async fn list_posts(
     State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    MaybeUser(user): MaybeUser,
     Query(pagination): Query<Pagination>,
-) -> Result<Json<Vec<PostResponse>>> {
-    let repo = PostRepository::new(pool);
-    let posts = repo.list(pagination.limit, pagination.offset, pagination.published_only).await?;
+) -> Result<impl IntoResponse> {
+    let posts = fetch_posts(&pool, &pagination, user).await?;
     
-    let response = posts.into_iter().map(PostResponse::from).collect();
-    Ok(Json(response))
+    // No Last-Modified: deleted posts, posts moving onto the page and read state don't show in
+    // the newest update time, so listings are only validated by their ETag
+    let (response, _) = build_responses(&pool, &reactions, posts, user).await?;
+    // Listings that can include drafts or per-user reactions must not end up in shared caches
+    let visibility = if pagination.published_only && user.is_none() { Visibility::Public } else { Visibility::Private };
+    
+    Ok((visibility, VARY_USER, Json(response)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/user/{user_id}",
+    params(("user_id" = Uuid, Path, description = "Author id"), Pagination),
+    security((), ("user_id" = [])),
+    responses(
+        (status = 200, description = "The user's posts, newest first", body = [PostResponse]),
+        (status = 404, description = "No such user", body = ErrorResponse),
+    ),
+)]
 async fn list_posts_by_user(
     State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    MaybeUser(user): MaybeUser,
     Path(user_id): Path<Uuid>,
     Query(pagination): Query<Pagination>,
-) -> Result<Json<Vec<PostResponse>>> {
+) -> Result<impl IntoResponse> {
+    let posts = fetch_posts_by_user(&pool, user_id, &pagination).await?;
+    
+    let (response, _) = build_responses(&pool, &reactions, posts, user).await?;
+    // Always includes drafts
+    let visibility = Visibility::Private;
+    
+    Ok((visibility, VARY_USER, Json(response)))
+}
+
+pub(super) async fn fetch_posts(pool: &PgPool, pagination: &Pagination, user: Option<CurrentUser>) -> Result<Vec<Post>> {
+    let repo = PostRepository::new(pool.clone());
+    match (pagination.unread_only, user) {
+        (false, _) => repo.list(pagination.limit, pagination.offset, pagination.published_only).await,
+        (true, Some(CurrentUser(user_id))) => {
+            repo.list_unread(user_id, pagination.limit, pagination.offset, pagination.published_only).await
+        }
+        (true, None) => Err(AppError::Unauthorized("unread_only requires an X-User-Id header".to_string())),
+    }
+}
+
+pub(super) async fn fetch_posts_by_user(pool: &PgPool, user_id: Uuid, pagination: &Pagination) -> Result<Vec<Post>> {
     // First check if the user exists
     let user_repo = UserRepository::new(pool.clone());
     if user_repo.find_by_id(user_id).await?.is_none() {
         return Err(AppError::NotFoundError(format!("User with id {} not found", user_id)));
     }
     
-    let post_repo = PostRepository::new(pool);
-    let posts = post_repo.find_by_author(user_id, pagination.limit, pagination.offset).await?;
-    
-    let response = posts.into_iter().map(PostResponse::from).collect();
-    Ok(Json(response))
+    let post_repo = PostRepository::new(pool.clone());
+    post_repo.find_by_author(user_id, pagination.limit, pagination.offset, false).await
 }
 
+#[utoipa::path(
+    post,
+    path = "",
+    params(AuthorParam),
+    request_body = CreatePostRequest,
+    responses(
+        (status = 200, description = "The new post", body = PostResponse),
+        (status = 400, description = "Invalid post or unknown author", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded, see Retry-After", body = ErrorResponse),
+    ),
+)]
 async fn create_post(
     State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    State(hooks): State<PostHooks>,
     // In a real app, you would get the user_id from the authenticated session
     // For simplicity, we'll use a header or query param
     Query(params): Query<AuthorParam>,
     Json(payload): Json<CreatePostRequest>,
 ) -> Result<Json<PostResponse>> {
-    // Validate the request
-    payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
-    
-    // Check if the author exists
-    let user_repo = UserRepository::new(pool.clone());
-    if user_repo.find_by_id(params.author_id).await?.is_none() {
-        return Err(AppError::BadRequest(format!("User with id {} does not exist", params.author_id)));
-    }
+    let post = create(&pool, &hooks, params.author_id, &payload).await?;
     
-    let post_repo = PostRepository::new(pool);
-    let post = post_repo.create(&payload, params.author_id).await?;
-    
-    Ok(Json(PostResponse::from(post)))
+    let (mut response, _) = build_responses(&pool, &reactions, vec![post], None).await?;
+    Ok(Json(response.remove(0)))
 }
 
-#[derive(Debug, Deserialize)]
-struct AuthorParam {
-    author_id: Uuid,
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct AuthorParam {
+    pub(super) author_id: Uuid,
 }
 
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    params(("id" = Uuid, Path, description = "Post id")),
+    security((), ("user_id" = [])),
+    responses(
+        (status = 200, description = "The post", body = PostResponse),
+        (status = 404, description = "No such post", body = ErrorResponse),
+    ),
+)]
 async fn get_post(
     State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    MaybeUser(user): MaybeUser,
     Path(id): Path<Uuid>,
-) -> Result<Json<PostResponse>> {
-    let repo = PostRepository::new(pool);
+) -> Result<impl IntoResponse> {
+    let repo = PostRepository::new(pool.clone());
     let post = repo.find_by_id(id).await?
         .ok_or_else(|| AppError::NotFoundError(format!("Post with id {} not found", id)))?;
     
-    Ok(Json(PostResponse::from(post)))
+    let visibility = if post.published && user.is_none() { Visibility::Public } else { Visibility::Private };
+    
+    let (mut response, last_modified) = build_responses(&pool, &reactions, vec![post], user).await?;
+    let last_modified = last_modified.map(LastModified);
+    
+    Ok((last_modified, visibility, VARY_USER, Json(response.remove(0))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/og-image.png",
+    params(("id" = Uuid, Path, description = "Post id")),
+    responses(
+        (status = 200, description = "A 1200x630 preview card for social media", content_type = "image/png"),
+        (status = 404, description = "No such post", body = ErrorResponse),
+    ),
+)]
+async fn get_og_image(
+    State(pool): State<PgPool>,
+    State(og_images): State<Arc<OgImages>>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let post = PostRepository::new(pool.clone()).find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Post with id {} not found", id)))?;
+    let author = UserRepository::new(pool).find_by_id(post.author_id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("User with id {} not found", post.author_id)))?;
+    
+    let card = Card {
+        title: &post.title,
+        author: &author.username,
+        published: post.created_at,
+    };
+    let png = og_images.get_or_render(post.id, post.updated_at, card).await
+        .map_err(|e| AppError::InternalError(format!("Failed to render OpenGraph image: {}", e)))?;
+    
+    let visibility = if post.published { Visibility::Public } else { Visibility::Private };
+    
+    Ok((LastModified(post.updated_at), visibility, [(header::CONTENT_TYPE, "image/png")], png))
 }
 
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    params(("id" = Uuid, Path, description = "Post id")),
+    request_body = UpdatePostRequest,
+    responses(
+        (status = 200, description = "The updated post", body = PostResponse),
+        (status = 400, description = "Invalid changes", body = ErrorResponse),
+        (status = 404, description = "No such post", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded, see Retry-After", body = ErrorResponse),
+    ),
+)]
 async fn update_post(
     State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    State(hooks): State<PostHooks>,
     Path(id): Path<Uuid>,
     // In a real app, you would verify that the user is the author of the post
     Json(payload): Json<UpdatePostRequest>,
 ) -> Result<Json<PostResponse>> {
+    let updated_post = update(&pool, &hooks, id, &payload).await?;
+    
+    let (mut response, _) = build_responses(&pool, &reactions, vec![updated_post], None).await?;
+    Ok(Json(response.remove(0)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    params(("id" = Uuid, Path, description = "Post id")),
+    responses(
+        (status = 200, description = "The post was deleted"),
+        (status = 404, description = "No such post", body = ErrorResponse),
+    ),
+)]
+async fn delete_post(
+    State(pool): State<PgPool>,
+    State(hooks): State<PostHooks>,
+    Path(id): Path<Uuid>,
+    // In a real app, you would verify that the user is the author of the post
+) -> Result<Json<serde_json::Value>> {
+    delete(&pool, &hooks, id).await?;
+    
+    Ok(Json(serde_json::json!({ "message": "Post deleted successfully" })))
+}
+
+// Everything that should happen when a post changes, whichever API changed it
+#[derive(Clone)]
+pub(crate) struct PostHooks {
+    pub(crate) notifier: Notifier,
+    pub(crate) webhooks: Webhooks,
+    pub(crate) events: Events,
+}
+
+impl PostHooks {
+    // Post changes go out to webhook subscribers and live event clients alike
+    async fn publish(&self, event: &str, post: &Post) {
+        self.webhooks.publish(event, post).await;
+        self.events.publish(event, post).await;
+    }
+    
+    // Mentions notify people once the post is published, and only for names the published
+    // version didn't mention yet
+    fn notify_mentions(&self, before: Option<&Post>, after: &Post) {
+        if !after.published {
+            return;
+        }
+        let mut usernames = notifications::mentions(&after.content);
+        if let Some(before) = before.filter(|p| p.published) {
+            for name in notifications::mentions(&before.content) {
+                usernames.remove(&name);
+            }
+        }
+        if !usernames.is_empty() {
+            self.notifier.notify(Event::Mentioned {
+                author_id: after.author_id,
+                post_id: after.id,
+                usernames: usernames.into_iter().collect(),
+            });
+        }
+    }
+}
+
+pub(crate) async fn create(pool: &PgPool, hooks: &PostHooks, author_id: Uuid, payload: &CreatePostRequest) -> Result<Post> {
+    // Validate the request
+    payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
+    
+    // Check if the author exists
+    let user_repo = UserRepository::new(pool.clone());
+    if user_repo.find_by_id(author_id).await?.is_none() {
+        return Err(AppError::BadRequest(format!("User with id {} does not exist", author_id)));
+    }
+    
+    let post_repo = PostRepository::new(pool.clone());
+    let post = post_repo.create(payload, author_id).await?;
+    metrics::counter!("posts_created_total").increment(1);
+    hooks.notify_mentions(None, &post);
+    hooks.publish(EVENT_POST_CREATED, &post).await;
+    if post.published {
+        metrics::counter!("posts_published_total").increment(1);
+        hooks.publish(EVENT_POST_PUBLISHED, &post).await;
+    }
+    
+    Ok(post)
+}
+
+pub(crate) async fn update(pool: &PgPool, hooks: &PostHooks, id: Uuid, payload: &UpdatePostRequest) -> Result<Post> {
     // Validate the request if any fields are provided
     if payload.title.is_some() || payload.content.is_some() {
         payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
     }
     
-    let repo = PostRepository::new(pool);
+    let repo = PostRepository::new(pool.clone());
     
     // Check if post exists
-    let _post = repo.find_by_id(id).await?
+    let post = repo.find_by_id(id).await?
         .ok_or_else(|| AppError::NotFoundError(format!("Post with id {} not found", id)))?;
     
     // In a real app, we would check if the current user is the author
     
-    let updated_post = repo.update(id, &payload).await?
+    let updated_post = repo.update(id, payload).await?
         .ok_or_else(|| AppError::NotFoundError(format!("Post with id {} not found", id)))?;
+    hooks.notify_mentions(Some(&post), &updated_post);
+    hooks.publish(EVENT_POST_UPDATED, &updated_post).await;
+    if updated_post.published && !post.published {
+        metrics::counter!("posts_published_total").increment(1);
+        hooks.publish(EVENT_POST_PUBLISHED, &updated_post).await;
+    }
     
-    Ok(Json(PostResponse::from(updated_post)))
+    Ok(updated_post)
 }
 
-async fn delete_post(
-    State(pool): State<PgPool>,
-    Path(id): Path<Uuid>,
-    // In a real app, you would verify that the user is the author of the post
-) -> Result<Json<serde_json::Value>> {
-    let repo = PostRepository::new(pool);
+pub(crate) async fn delete(pool: &PgPool, hooks: &PostHooks, id: Uuid) -> Result<()> {
+    let repo = PostRepository::new(pool.clone());
     
     // Check if post exists
-    let _post = repo.find_by_id(id).await?
+    let post = repo.find_by_id(id).await?
         .ok_or_else(|| AppError::NotFoundError(format!("Post with id {} not found", id)))?;
     
     // In a real app, we would check if the current user is the author
@@ -144,6 +434,221 @@ async fn delete_post(
     if !deleted {
         return Err(AppError::NotFoundError(format!("Post with id {} not found", id)));
     }
+    hooks.publish(EVENT_POST_DELETED, &post).await;
     
-    Ok(Json(serde_json::json!({ "message": "Post deleted successfully" })))
+    Ok(())
+}
+
+// PUT is idempotent: reacting twice with the same kind keeps a single reaction
+#[utoipa::path(
+    put,
+    path = "/{id}/reactions/{kind}",
+    params(("id" = Uuid, Path, description = "Post id"), ("kind" = String, Path, description = "A configured reaction kind, e.g. `like`")),
+    security(("user_id" = [])),
+    responses(
+        (status = 200, description = "The post's reactions", body = ReactionsResponse),
+        (status = 400, description = "Unknown reaction kind", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown user", body = ErrorResponse),
+        (status = 404, description = "No such post", body = ErrorResponse),
+    ),
+)]
+async fn add_reaction(
+    State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    State(hooks): State<PostHooks>,
+    user: CurrentUser,
+    Path((id, kind)): Path<(Uuid, String)>,
+) -> Result<Json<ReactionsResponse>> {
+    let post = find_reactable_post(&pool, &reactions, id, &kind).await?;
+    if ReactionRepository::new(pool.clone()).add(post.id, user.0, &kind).await? {
+        hooks.notifier.notify(Event::Reacted { user_id: user.0, post_id: post.id });
+    }
+    
+    reactions_response(&pool, &reactions, post, user).await
+}
+
+// Removing a reaction that was never left is not an error
+#[utoipa::path(
+    delete,
+    path = "/{id}/reactions/{kind}",
+    params(("id" = Uuid, Path, description = "Post id"), ("kind" = String, Path, description = "A configured reaction kind, e.g. `like`")),
+    security(("user_id" = [])),
+    responses(
+        (status = 200, description = "The post's reactions", body = ReactionsResponse),
+        (status = 400, description = "Unknown reaction kind", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown user", body = ErrorResponse),
+        (status = 404, description = "No such post", body = ErrorResponse),
+    ),
+)]
+async fn remove_reaction(
+    State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    user: CurrentUser,
+    Path((id, kind)): Path<(Uuid, String)>,
+) -> Result<Json<ReactionsResponse>> {
+    let post = find_reactable_post(&pool, &reactions, id, &kind).await?;
+    ReactionRepository::new(pool.clone()).remove(post.id, user.0, &kind).await?;
+    
+    reactions_response(&pool, &reactions, post, user).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}/read",
+    params(("id" = Uuid, Path, description = "Post id")),
+    security(("user_id" = [])),
+    responses(
+        (status = 200, description = "The post's read state", body = ReadStatusResponse),
+        (status = 401, description = "Missing or unknown user", body = ErrorResponse),
+        (status = 404, description = "No such post", body = ErrorResponse),
+    ),
+)]
+async fn mark_read(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReadStatusResponse>> {
+    let post = PostRepository::new(pool.clone()).find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Post with id {} not found", id)))?;
+    let read_at = ReadRepository::new(pool).mark_read(user.0, post.id).await?;
+    
+    Ok(Json(ReadStatusResponse { post_id: post.id, read: true, read_at: Some(read_at) }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}/read",
+    params(("id" = Uuid, Path, description = "Post id")),
+    security(("user_id" = [])),
+    responses(
+        (status = 200, description = "The post's read state", body = ReadStatusResponse),
+        (status = 401, description = "Missing or unknown user", body = ErrorResponse),
+        (status = 404, description = "No such post", body = ErrorResponse),
+    ),
+)]
+async fn mark_unread(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReadStatusResponse>> {
+    let post = PostRepository::new(pool.clone()).find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Post with id {} not found", id)))?;
+    ReadRepository::new(pool).mark_unread(user.0, post.id).await?;
+    
+    Ok(Json(ReadStatusResponse { post_id: post.id, read: false, read_at: None }))
+}
+
+// Bulk variant used for "mark all as read" and for importing read state kept by a client
+#[utoipa::path(
+    post,
+    path = "/read",
+    request_body = MarkReadRequest,
+    security(("user_id" = [])),
+    responses(
+        (status = 200, description = "How many posts were newly marked", body = MarkReadResponse),
+        (status = 400, description = "Too many post ids, or both `post_ids` and `all`", body = ErrorResponse),
+        (status = 401, description = "Missing or unknown user", body = ErrorResponse),
+    ),
+)]
+async fn mark_many_read(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Json(payload): Json<MarkReadRequest>,
+) -> Result<Json<MarkReadResponse>> {
+    payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
+    if payload.all && !payload.post_ids.is_empty() {
+        return Err(AppError::BadRequest("Pass either post_ids or all, not both".to_string()));
+    }
+    
+    let repo = ReadRepository::new(pool);
+    let marked = if payload.all {
+        repo.mark_all_read(user.0).await?
+    } else {
+        repo.mark_many_read(user.0, &payload.post_ids).await?
+    };
+    
+    Ok(Json(MarkReadResponse { marked }))
+}
+
+async fn find_reactable_post(pool: &PgPool, reactions: &ReactionsConfig, id: Uuid, kind: &str) -> Result<Post> {
+    if reactions.find(kind).is_none() {
+        return Err(AppError::BadRequest(format!("Unknown reaction kind: {}", kind)));
+    }
+    
+    PostRepository::new(pool.clone()).find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Post with id {} not found", id)))
+}
+
+async fn reactions_response(
+    pool: &PgPool,
+    reactions: &ReactionsConfig,
+    post: Post,
+    user: CurrentUser,
+) -> Result<Json<ReactionsResponse>> {
+    let (mut response, _) = build_responses(pool, reactions, vec![post], Some(user)).await?;
+    let post = response.remove(0);
+    
+    Ok(Json(ReactionsResponse {
+        post_id: post.id,
+        reactions: post.reactions,
+        my_reactions: post.my_reactions.unwrap_or_default(),
+    }))
+}
+
+// Attaches reaction counts, and the user's own reactions and read state when known, to a page
+// of posts. Also returns the latest change to the posts or their reactions, which is only a
+// valid Last-Modified for a single post.
+pub(super) async fn build_responses(
+    pool: &PgPool,
+    reactions: &ReactionsConfig,
+    posts: Vec<Post>,
+    user: Option<CurrentUser>,
+) -> Result<(Vec<PostResponse>, Option<DateTime<Utc>>)> {
+    let repo = ReactionRepository::new(pool.clone());
+    let post_ids: Vec<Uuid> = posts.iter().map(|p| p.id).collect();
+    
+    let mut last_modified = posts.iter().map(|p| p.updated_at).max();
+    let mut counts: HashMap<(Uuid, String), i64> = HashMap::new();
+    for count in repo.counts_for_posts(&post_ids).await? {
+        last_modified = last_modified.max(Some(count.updated_at));
+        counts.insert((count.post_id, count.kind), count.count);
+    }
+    
+    let (mine, read) = match user {
+        Some(CurrentUser(user_id)) => {
+            let mine = repo.find_by_user(user_id, &post_ids).await?;
+            let read = ReadRepository::new(pool.clone()).find_read(user_id, &post_ids).await?;
+            (Some(mine), Some(read))
+        }
+        None => (None, None),
+    };
+    
+    let response = posts
+        .into_iter()
+        .map(|post| {
+            let mut response = PostResponse::from(post);
+            response.reactions = reactions
+                .kinds
+                .iter()
+                .map(|kind| ReactionSummary {
+                    kind: kind.name.clone(),
+                    emoji: kind.emoji.clone(),
+                    count: counts.get(&(response.id, kind.name.clone())).copied().unwrap_or(0),
+                })
+                .collect();
+            response.my_reactions = mine.as_ref().map(|mine| {
+                // Keep the configured order, and drop kinds that are no longer offered
+                reactions
+                    .kinds
+                    .iter()
+                    .filter(|kind| mine.iter().any(|(id, k)| *id == response.id && *k == kind.name))
+                    .map(|kind| kind.name.clone())
+                    .collect()
+            });
+            response.read = read.as_ref().map(|read| read.contains(&response.id));
+            response
+        })
+        .collect();
+    
+    Ok((response, last_modified))
 }
\ No newline at end of file
@@ -0,0 +1,257 @@
+use std::fmt::Write;
+use std::sync::Arc;
+
+use axum::{
+    extract::{FromRef, Path, State},
+    handler::Handler,
+    http::header,
+    response::IntoResponse,
+    routing::{delete, get, post},
+    Json, Router,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::auth::{CurrentUser, MaybeUser};
+use crate::config::AppConfig;
+use crate::db::{PostRepository, ReadingListRepository};
+use crate::errors::{AppError, Result};
+use crate::middleware::http_cache::Visibility;
+use crate::middleware::HttpCacheLayer;
+use crate::models::reading_list::{
+    AddReadingListItemRequest, CreateReadingListRequest, ReadingList, ReadingListResponse, ReadingListSummary,
+    ReorderReadingListRequest, UpdateReadingListRequest,
+};
+
+#[derive(Clone, FromRef)]
+struct ReadingListState {
+    pool: PgPool,
+    base_url: Arc<str>,
+}
+
+// Owners see their whole list; anyone else only sees published posts
+const VARY_USER: [(header::HeaderName, &str); 1] = [(header::VARY, "X-User-Id")];
+
+// Mounted under /api/users next to the user routes
+pub fn create_router(pool: PgPool, config: &AppConfig) -> Router {
+    let cache = HttpCacheLayer::new(&config.http_cache.lists, &config.http_cache.drafts);
+    let state = ReadingListState {
+        pool,
+        base_url: Arc::from(config.site.base_url()),
+    };
+
+    Router::new()
+        .route("/:id/lists", get(list_reading_lists).post(create_reading_list))
+        .route(
+            "/:id/lists/:list_id",
+            get(get_reading_list.layer(cache.clone())).put(update_reading_list).delete(delete_reading_list),
+        )
+        .route("/:id/lists/:list_id/items", post(add_item).put(reorder_items))
+        .route("/:id/lists/:list_id/items/:post_id", delete(remove_item))
+        .route("/:id/lists/:list_id/export.md", get(export_markdown.layer(cache)))
+        .with_state(state)
+}
+
+async fn list_reading_lists(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Path(user_id): Path<Uuid>,
+) -> Result<Json<Vec<ReadingListSummary>>> {
+    require_owner(user, user_id)?;
+    
+    let lists = ReadingListRepository::new(pool).find_by_owner(user_id).await?;
+    Ok(Json(lists))
+}
+
+async fn create_reading_list(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Path(user_id): Path<Uuid>,
+    Json(payload): Json<CreateReadingListRequest>,
+) -> Result<Json<ReadingListResponse>> {
+    require_owner(user, user_id)?;
+    payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
+    
+    let list = ReadingListRepository::new(pool).create(user_id, &payload).await?;
+    Ok(Json(ReadingListResponse::new(list, Vec::new())))
+}
+
+async fn get_reading_list(
+    State(pool): State<PgPool>,
+    MaybeUser(user): MaybeUser,
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse> {
+    let (list, is_owner) = find_visible_list(&pool, user, user_id, list_id).await?;
+    let items = ReadingListRepository::new(pool).items(list.id, !is_owner).await?;
+    
+    let visibility = if list.shared && !is_owner { Visibility::Public } else { Visibility::Private };
+    
+    Ok((visibility, VARY_USER, Json(ReadingListResponse::new(list, items))))
+}
+
+async fn update_reading_list(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<UpdateReadingListRequest>,
+) -> Result<Json<ReadingListResponse>> {
+    payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
+    find_owned_list(&pool, user, user_id, list_id).await?;
+    
+    let repo = ReadingListRepository::new(pool);
+    let list = repo.update(list_id, &payload).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Reading list with id {} not found", list_id)))?;
+    let items = repo.items(list.id, false).await?;
+    
+    Ok(Json(ReadingListResponse::new(list, items)))
+}
+
+async fn delete_reading_list(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<serde_json::Value>> {
+    find_owned_list(&pool, user, user_id, list_id).await?;
+    
+    let deleted = ReadingListRepository::new(pool).delete(list_id).await?;
+    if !deleted {
+        return Err(AppError::NotFoundError(format!("Reading list with id {} not found", list_id)));
+    }
+    
+    Ok(Json(serde_json::json!({ "message": "Reading list deleted successfully" })))
+}
+
+// Adding a post that is already in the list leaves it where it is
+async fn add_item(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<AddReadingListItemRequest>,
+) -> Result<Json<ReadingListResponse>> {
+    find_owned_list(&pool, user, user_id, list_id).await?;
+    
+    if PostRepository::new(pool.clone()).find_by_id(payload.post_id).await?.is_none() {
+        return Err(AppError::BadRequest(format!("Post with id {} does not exist", payload.post_id)));
+    }
+    
+    let repo = ReadingListRepository::new(pool);
+    repo.add_item(list_id, payload.post_id).await?;
+    
+    owner_view(&repo, list_id).await
+}
+
+async fn remove_item(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Path((user_id, list_id, post_id)): Path<(Uuid, Uuid, Uuid)>,
+) -> Result<Json<ReadingListResponse>> {
+    find_owned_list(&pool, user, user_id, list_id).await?;
+    
+    let repo = ReadingListRepository::new(pool);
+    if !repo.remove_item(list_id, post_id).await? {
+        return Err(AppError::NotFoundError(format!("Post with id {} is not in this reading list", post_id)));
+    }
+    
+    owner_view(&repo, list_id).await
+}
+
+async fn reorder_items(
+    State(pool): State<PgPool>,
+    user: CurrentUser,
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+    Json(payload): Json<ReorderReadingListRequest>,
+) -> Result<Json<ReadingListResponse>> {
+    find_owned_list(&pool, user, user_id, list_id).await?;
+    
+    let repo = ReadingListRepository::new(pool);
+    if !repo.reorder(list_id, &payload.post_ids).await? {
+        return Err(AppError::BadRequest("post_ids must list every item in the reading list exactly once".to_string()));
+    }
+    
+    owner_view(&repo, list_id).await
+}
+
+async fn export_markdown(
+    State(pool): State<PgPool>,
+    State(base_url): State<Arc<str>>,
+    MaybeUser(user): MaybeUser,
+    Path((user_id, list_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse> {
+    let (list, is_owner) = find_visible_list(&pool, user, user_id, list_id).await?;
+    let items = ReadingListRepository::new(pool).items(list.id, !is_owner).await?;
+    
+    let mut markdown = format!("# {}\n\n", escape_markdown(&list.name.replace(['\r', '\n'], " ")));
+    if let Some(description) = &list.description {
+        markdown.push_str(&escape_markdown(description));
+        markdown.push_str("\n\n");
+    }
+    for item in &items {
+        let title = escape_markdown(&item.title.replace(['\r', '\n'], " "));
+        let _ = writeln!(markdown, "- [{}]({}/posts/{})", title, base_url, item.post_id);
+    }
+    
+    let visibility = if list.shared && !is_owner { Visibility::Public } else { Visibility::Private };
+    let headers = [
+        (header::CONTENT_TYPE, "text/markdown; charset=utf-8"),
+        (header::VARY, "X-User-Id"),
+    ];
+    
+    Ok((visibility, headers, markdown))
+}
+
+fn require_owner(user: CurrentUser, user_id: Uuid) -> Result<()> {
+    if user.0 != user_id {
+        return Err(AppError::Forbidden("Reading lists can only be managed by their owner".to_string()));
+    }
+    Ok(())
+}
+
+// Looks up a list the current user owns, for endpoints that change it
+async fn find_owned_list(pool: &PgPool, user: CurrentUser, user_id: Uuid, list_id: Uuid) -> Result<ReadingList> {
+    require_owner(user, user_id)?;
+    
+    ReadingListRepository::new(pool.clone()).find_by_id(list_id).await?
+        .filter(|list| list.owner_id == user_id)
+        .ok_or_else(|| AppError::NotFoundError(format!("Reading list with id {} not found", list_id)))
+}
+
+// Private lists are reported as missing to everyone but their owner
+async fn find_visible_list(
+    pool: &PgPool,
+    user: Option<CurrentUser>,
+    user_id: Uuid,
+    list_id: Uuid,
+) -> Result<(ReadingList, bool)> {
+    let list = ReadingListRepository::new(pool.clone()).find_by_id(list_id).await?
+        .filter(|list| list.owner_id == user_id)
+        .ok_or_else(|| AppError::NotFoundError(format!("Reading list with id {} not found", list_id)))?;
+    
+    let is_owner = matches!(user, Some(CurrentUser(id)) if id == list.owner_id);
+    if !list.shared && !is_owner {
+        return Err(AppError::NotFoundError(format!("Reading list with id {} not found", list_id)));
+    }
+    
+    Ok((list, is_owner))
+}
+
+async fn owner_view(repo: &ReadingListRepository, list_id: Uuid) -> Result<Json<ReadingListResponse>> {
+    let list = repo.find_by_id(list_id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Reading list with id {} not found", list_id)))?;
+    let items = repo.items(list_id, false).await?;
+    
+    Ok(Json(ReadingListResponse::new(list, items)))
+}
+
+// Backslash-escapes the characters Markdown could read as formatting, links or raw HTML,
+// so user-provided text comes out as written
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\`*_{}[]()<>#+-.!|~&".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
\ No newline at end of file
@@ -0,0 +1,193 @@
+use std::fmt::Write;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    handler::Handler,
+    http::header,
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::PgPool;
+
+use crate::config::{AppConfig, RobotsConfig};
+use crate::db::{PostRepository, UserRepository};
+use crate::errors::{AppError, Result};
+use crate::feed::escape_xml;
+use crate::middleware::http_cache::LastModified;
+use crate::middleware::HttpCacheLayer;
+
+// Maximum number of URLs in a single sitemap, from the sitemaps.org protocol
+const MAX_URLS_PER_SITEMAP: i64 = 50_000;
+
+const XML_CONTENT_TYPE: &str = "application/xml; charset=utf-8";
+
+#[derive(Clone)]
+struct SitemapState {
+    pool: PgPool,
+    base_url: Arc<str>,
+    robots: Arc<RobotsConfig>,
+}
+
+pub fn create_router(pool: PgPool, config: &AppConfig) -> Router {
+    let state = SitemapState {
+        pool,
+        base_url: Arc::from(config.site.base_url()),
+        robots: Arc::new(config.robots.clone()),
+    };
+    let cache = HttpCacheLayer::new(&config.http_cache.sitemap, &config.http_cache.drafts);
+
+    Router::new()
+        .route("/sitemap.xml", get(sitemap.layer(cache.clone())))
+        .route("/sitemaps/:file", get(child_sitemap.layer(cache.clone())))
+        .route("/robots.txt", get(robots.layer(cache)))
+        .with_state(state)
+}
+
+struct UrlEntry {
+    loc: String,
+    lastmod: Option<DateTime<Utc>>,
+}
+
+// The sitemap is one sequence of URLs: the home page, every published post and
+// the page of every author with published posts. Small sites get it as a single urlset; past the protocol limit it is
+// split into numbered child sitemaps behind a sitemap index.
+async fn sitemap(State(state): State<SitemapState>) -> Result<impl IntoResponse> {
+    let total = total_urls(&state).await?;
+
+    if total <= MAX_URLS_PER_SITEMAP {
+        let entries = load_entries(&state, 0, total).await?;
+        return Ok(xml_response(last_modified(&entries), render_urlset(&entries)));
+    }
+
+    let pages = (total + MAX_URLS_PER_SITEMAP - 1) / MAX_URLS_PER_SITEMAP;
+    Ok(xml_response(None, render_index(&state.base_url, pages)))
+}
+
+async fn child_sitemap(
+    State(state): State<SitemapState>,
+    Path(file): Path<String>,
+) -> Result<impl IntoResponse> {
+    let not_found = || AppError::NotFoundError(format!("Sitemap {} not found", file));
+
+    let page = file
+        .strip_suffix(".xml")
+        .and_then(|n| n.parse::<i64>().ok())
+        .filter(|n| *n >= 1)
+        .ok_or_else(not_found)?;
+
+    let start = (page - 1) * MAX_URLS_PER_SITEMAP;
+    if start >= total_urls(&state).await? {
+        return Err(not_found());
+    }
+
+    let entries = load_entries(&state, start, MAX_URLS_PER_SITEMAP).await?;
+    Ok(xml_response(last_modified(&entries), render_urlset(&entries)))
+}
+
+async fn robots(State(state): State<SitemapState>) -> impl IntoResponse {
+    let mut body = String::from("User-agent: *\n");
+    for path in &state.robots.allow {
+        let _ = writeln!(body, "Allow: {}", path);
+    }
+    for path in &state.robots.disallow {
+        let _ = writeln!(body, "Disallow: {}", path);
+    }
+    let _ = writeln!(body, "\nSitemap: {}/sitemap.xml", state.base_url);
+
+    ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], body)
+}
+
+async fn total_urls(state: &SitemapState) -> Result<i64> {
+    let posts = PostRepository::new(state.pool.clone()).count_published().await?;
+    let authors = UserRepository::new(state.pool.clone()).count_with_published_posts().await?;
+    Ok(1 + posts + authors)
+}
+
+// Loads `limit` entries starting at position `start` of the URL sequence
+async fn load_entries(state: &SitemapState, start: i64, limit: i64) -> Result<Vec<UrlEntry>> {
+    let mut entries = Vec::new();
+    let mut start = start;
+    let mut limit = limit;
+
+    if start == 0 && limit > 0 {
+        entries.push(UrlEntry {
+            loc: format!("{}/", state.base_url),
+            lastmod: None,
+        });
+        limit -= 1;
+    } else {
+        start -= 1;
+    }
+
+    // Unpublished posts are filtered by the queries, and posts of deleted users are
+    // removed by the ON DELETE CASCADE on posts.author_id
+    let post_repo = PostRepository::new(state.pool.clone());
+    if limit > 0 {
+        let posts = post_repo.list_published_timestamps(limit, start).await?;
+        limit -= posts.len() as i64;
+        entries.extend(posts.into_iter().map(|(id, updated_at)| UrlEntry {
+            loc: format!("{}/posts/{}", state.base_url, id),
+            lastmod: Some(updated_at),
+        }));
+    }
+
+    if limit > 0 {
+        let post_count = post_repo.count_published().await?;
+        let author_start = (start - post_count).max(0);
+        let authors = UserRepository::new(state.pool.clone())
+            .list_with_published_posts(limit, author_start)
+            .await?;
+        entries.extend(authors.into_iter().map(|(id, updated_at)| UrlEntry {
+            loc: format!("{}/authors/{}", state.base_url, id),
+            lastmod: Some(updated_at),
+        }));
+    }
+
+    Ok(entries)
+}
+
+fn last_modified(entries: &[UrlEntry]) -> Option<LastModified> {
+    entries.iter().filter_map(|e| e.lastmod).max().map(LastModified)
+}
+
+fn xml_response(last_modified: Option<LastModified>, body: String) -> impl IntoResponse {
+    (last_modified, [(header::CONTENT_TYPE, XML_CONTENT_TYPE)], body)
+}
+
+fn render_urlset(entries: &[UrlEntry]) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#);
+    xml.push('\n');
+
+    for entry in entries {
+        let _ = write!(xml, "<url><loc>{}</loc>", escape_xml(&entry.loc));
+        if let Some(lastmod) = entry.lastmod {
+            let _ = write!(xml, "<lastmod>{}</lastmod>", lastmod.to_rfc3339_opts(SecondsFormat::Secs, true));
+        }
+        xml.push_str("</url>\n");
+    }
+
+    xml.push_str("</urlset>\n");
+    xml
+}
+
+fn render_index(base_url: &str, pages: i64) -> String {
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">"#);
+    xml.push('\n');
+
+    for page in 1..=pages {
+        let loc = format!("{}/sitemaps/{}.xml", base_url, page);
+        let _ = writeln!(xml, "<sitemap><loc>{}</loc></sitemap>", escape_xml(&loc));
+    }
+
+    xml.push_str("</sitemapindex>\n");
+    xml
+}
@@ -1,104 +1,258 @@
 use axum::{
-    extract::{Path, Query, State},
-    routing::get,
+    extract::{FromRef, Path, Query, State},
+    handler::Handler,
+    routing::{get, post},
     Json, Router,
 };
 use serde::Deserialize;
 use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use utoipa::{IntoParams, OpenApi};
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::db::UserRepository;
-use crate::errors::{AppError, Result};
-use crate::models::user::{CreateUserRequest, UpdateUserRequest, UserResponse};
+use crate::db::{FollowRepository, ImageRepository, MediaRepository, UserRepository};
+use crate::errors::{AppError, ErrorResponse, Result};
+use crate::images;
+use crate::middleware::RateLimiter;
+use crate::models::follow::FollowCounts;
+use crate::models::image::ImageVariant;
+use crate::models::user::{CreateUserRequest, UpdateUserRequest, User, UserResponse};
+use crate::models::webhook::EVENT_USER_CREATED;
+use crate::storage::MediaStore;
+use crate::webhooks::Webhooks;
 
-pub fn create_router(pool: PgPool) -> Router {
+#[derive(Clone, FromRef)]
+pub struct UserState {
+    pool: PgPool,
+    media_store: Arc<dyn MediaStore>,
+    webhooks: Webhooks,
+}
+
+impl UserState {
+    pub(super) fn new(pool: PgPool, media_store: Arc<dyn MediaStore>, webhooks: Webhooks) -> Self {
+        Self { pool, media_store, webhooks }
+    }
+}
+
+pub fn create_router(state: UserState, limiter: &RateLimiter) -> Router {
+    Router::new()
+        .route("/", get(list_users))
+        .merge(common_routes(limiter))
+        .with_state(state)
+}
+
+// Routes that respond the same in every API version
+pub(super) fn common_routes(limiter: &RateLimiter) -> Router<UserState> {
     Router::new()
-        .route("/", get(list_users).post(create_user))
+        .route("/", post(create_user.layer(limiter.ip_layer("signup"))))
         .route("/:id", get(get_user).put(update_user).delete(delete_user))
-        .with_state(pool)
 }
 
-#[derive(Debug, Deserialize)]
-struct Pagination {
+// OpenAPI description of the routes above, served by `api::openapi`
+#[derive(OpenApi)]
+#[openapi(paths(list_users, create_user, get_user, update_user, delete_user))]
+pub(super) struct UsersApi;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(super) struct Pagination {
     #[serde(default = "default_limit")]
-    limit: i64,
+    #[param(default = 10)]
+    pub(super) limit: i64,
     #[serde(default)]
-    offset: i64,
+    pub(super) offset: i64,
 }
 
 fn default_limit() -> i64 {
     10
 }
 
+#[utoipa::path(
+    get,
+    path = "",
+    params(Pagination),
+    responses((status = 200, description = "Users", body = [UserResponse])),
+)]
 async fn list_users(
     State(pool): State<PgPool>,
     Query(pagination): Query<Pagination>,
 ) -> Result<Json<Vec<UserResponse>>> {
-    let repo = UserRepository::new(pool);
+    let repo = UserRepository::new(pool.clone());
     let users = repo.list(pagination.limit, pagination.offset).await?;
     
-    let response = users.into_iter().map(UserResponse::from).collect();
+    let response = with_follow_counts(&pool, users).await?;
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    post,
+    path = "",
+    request_body = CreateUserRequest,
+    responses(
+        (status = 200, description = "The new user", body = UserResponse),
+        (status = 400, description = "Invalid user or email already taken", body = ErrorResponse),
+        (status = 429, description = "Rate limit exceeded, see Retry-After", body = ErrorResponse),
+    ),
+)]
 async fn create_user(
     State(pool): State<PgPool>,
+    State(webhooks): State<Webhooks>,
     Json(payload): Json<CreateUserRequest>,
 ) -> Result<Json<UserResponse>> {
-    // Validate the request
-    payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
-    
-    // Check if user with email already exists
-    let repo = UserRepository::new(pool);
-    let existing = repo.find_by_email(&payload.email).await?;
+    let user = create(&pool, &webhooks, &payload).await?;
     
-    if existing.is_some() {
-        return Err(AppError::BadRequest("User with this email already exists".to_string()));
-    }
-    
-    let user = repo.create(&payload).await?;
     Ok(Json(UserResponse::from(user)))
 }
 
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    params(("id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "The user", body = UserResponse),
+        (status = 404, description = "No such user", body = ErrorResponse),
+    ),
+)]
 async fn get_user(
     State(pool): State<PgPool>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<UserResponse>> {
-    let repo = UserRepository::new(pool);
+    let repo = UserRepository::new(pool.clone());
     let user = repo.find_by_id(id).await?
         .ok_or_else(|| AppError::NotFoundError(format!("User with id {} not found", id)))?;
     
-    Ok(Json(UserResponse::from(user)))
+    let mut response = with_follow_counts(&pool, vec![user]).await?;
+    Ok(Json(response.remove(0)))
 }
 
+#[utoipa::path(
+    put,
+    path = "/{id}",
+    params(("id" = Uuid, Path, description = "User id")),
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 200, description = "The updated user", body = UserResponse),
+        (status = 400, description = "Invalid changes", body = ErrorResponse),
+        (status = 404, description = "No such user", body = ErrorResponse),
+    ),
+)]
 async fn update_user(
     State(pool): State<PgPool>,
     Path(id): Path<Uuid>,
     Json(payload): Json<UpdateUserRequest>,
 ) -> Result<Json<UserResponse>> {
-    // Validate the request if any fields are provided
-    if payload.username.is_some() || payload.email.is_some() || payload.password.is_some() {
-        payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
-    }
+    let user = update(&pool, id, &payload).await?;
     
-    let repo = UserRepository::new(pool);
-    let user = repo.update(id, &payload).await?
-        .ok_or_else(|| AppError::NotFoundError(format!("User with id {} not found", id)))?;
-    
-    Ok(Json(UserResponse::from(user)))
+    let mut response = with_follow_counts(&pool, vec![user]).await?;
+    Ok(Json(response.remove(0)))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    params(("id" = Uuid, Path, description = "User id")),
+    responses(
+        (status = 200, description = "The user and their media were deleted"),
+        (status = 404, description = "No such user", body = ErrorResponse),
+    ),
+)]
 async fn delete_user(
     State(pool): State<PgPool>,
+    State(media_store): State<Arc<dyn MediaStore>>,
     Path(id): Path<Uuid>,
 ) -> Result<Json<serde_json::Value>> {
-    let repo = UserRepository::new(pool);
+    delete(&pool, media_store.as_ref(), id).await?;
+    
+    Ok(Json(serde_json::json!({ "message": "User deleted successfully" })))
+}
+
+// The user operations below are shared by the REST handlers and GraphQL mutations
+
+pub(crate) async fn create(pool: &PgPool, webhooks: &Webhooks, payload: &CreateUserRequest) -> Result<User> {
+    // Validate the request
+    payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
+    
+    // Check if user with email already exists
+    let repo = UserRepository::new(pool.clone());
+    let existing = repo.find_by_email(&payload.email).await?;
+    
+    if existing.is_some() {
+        return Err(AppError::BadRequest("User with this email already exists".to_string()));
+    }
+    
+    let user = repo.create(payload).await?;
+    metrics::counter!("users_registered_total").increment(1);
+    webhooks.publish(EVENT_USER_CREATED, UserResponse::from(user.clone())).await;
+    
+    Ok(user)
+}
+
+pub(crate) async fn update(pool: &PgPool, id: Uuid, payload: &UpdateUserRequest) -> Result<User> {
+    // Validate the request if any fields are provided
+    if payload.username.is_some() || payload.email.is_some() || payload.password.is_some() {
+        payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
+    }
+    
+    let repo = UserRepository::new(pool.clone());
+    repo.update(id, payload).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("User with id {} not found", id)))
+}
+
+pub(crate) async fn delete(pool: &PgPool, media_store: &dyn MediaStore, id: Uuid) -> Result<()> {
+    // The media and image rows go away with the user via ON DELETE CASCADE, but the files don't
+    let media = MediaRepository::new(pool.clone()).find_by_uploader(id).await?;
+    let image_repo = ImageRepository::new(pool.clone());
+    let mut image_files: HashMap<Uuid, Vec<ImageVariant>> = image_repo
+        .find_ids_by_uploader(id)
+        .await?
+        .into_iter()
+        .map(|image_id| (image_id, Vec::new()))
+        .collect();
+    for variant in image_repo.find_variants_by_uploader(id).await? {
+        image_files.entry(variant.image_id).or_default().push(variant);
+    }
+    
+    let repo = UserRepository::new(pool.clone());
     let deleted = repo.delete(id).await?;
     
     if !deleted {
         return Err(AppError::NotFoundError(format!("User with id {} not found", id)));
     }
     
-    Ok(Json(serde_json::json!({ "message": "User deleted successfully" })))
+    for item in media {
+        if let Err(e) = media_store.delete(&item.storage_key).await {
+            tracing::error!("Failed to remove media file {}: {}", item.storage_key, e);
+        }
+    }
+    for (image_id, variants) in image_files {
+        images::delete_files(media_store, image_id, &variants).await;
+    }
+    
+    Ok(())
+}
+
+pub(super) async fn with_follow_counts(pool: &PgPool, users: Vec<User>) -> Result<Vec<UserResponse>> {
+    let ids: Vec<Uuid> = users.iter().map(|u| u.id).collect();
+    let counts: HashMap<Uuid, FollowCounts> = FollowRepository::new(pool.clone())
+        .counts(&ids)
+        .await?
+        .into_iter()
+        .map(|c| (c.user_id, c))
+        .collect();
+    
+    let response = users
+        .into_iter()
+        .map(|user| {
+            let mut response = UserResponse::from(user);
+            if let Some(counts) = counts.get(&response.id) {
+                response.follower_count = counts.follower_count;
+                response.following_count = counts.following_count;
+            }
+            response
+        })
+        .collect();
+    
+    Ok(response)
 }
\ No newline at end of file
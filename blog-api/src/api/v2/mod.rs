@@ -0,0 +1,4 @@
+// Version 2 of the REST API. Only the resources whose responses changed have their own
+// handlers here; the rest of the routes are shared with v1.
+pub mod posts;
+pub mod users;
\ No newline at end of file
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, Query, State},
+    handler::Handler,
+    response::IntoResponse,
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::auth::{CurrentUser, MaybeUser};
+use crate::api::posts::{self, AuthorParam, Pagination, PostHooks, PostState, VARY_USER};
+use crate::config::{AppConfig, ReactionsConfig};
+use crate::db::{PostRepository, UserRepository};
+use crate::errors::{AppError, Result};
+use crate::middleware::http_cache::{LastModified, Visibility};
+use crate::middleware::RateLimiter;
+use crate::models::post::{CreatePostRequest, Post, UpdatePostRequest};
+use crate::models::v2::{Author, Page, PostResponse};
+
+pub fn create_router(state: PostState, config: &AppConfig, limiter: &RateLimiter) -> Router {
+    let (list_cache, post_cache) = posts::cache_layers(config);
+    let writes = limiter.layer("posts");
+
+    Router::new()
+        .route("/", get(list_posts.layer(list_cache.clone())).post(create_post.layer(writes.clone())))
+        .route("/:id", get(get_post.layer(post_cache)).put(update_post.layer(writes)))
+        .route("/user/:user_id", get(list_posts_by_user.layer(list_cache)))
+        .merge(posts::common_routes(config))
+        .with_state(state)
+}
+
+async fn list_posts(
+    State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    MaybeUser(user): MaybeUser,
+    Query(pagination): Query<Pagination>,
+) -> Result<impl IntoResponse> {
+    let posts = posts::fetch_posts(&pool, &overfetch(&pagination), user).await?;
+    
+    // Listings have no Last-Modified, see `posts::list_posts`
+    let (response, _) = build_responses(&pool, &reactions, posts, user).await?;
+    let visibility = if pagination.published_only && user.is_none() { Visibility::Public } else { Visibility::Private };
+    let page = Page::new(response, pagination.limit, pagination.offset);
+    
+    Ok((visibility, VARY_USER, Json(page)))
+}
+
+async fn list_posts_by_user(
+    State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    MaybeUser(user): MaybeUser,
+    Path(user_id): Path<Uuid>,
+    Query(pagination): Query<Pagination>,
+) -> Result<impl IntoResponse> {
+    let posts = posts::fetch_posts_by_user(&pool, user_id, &overfetch(&pagination)).await?;
+    
+    let (response, _) = build_responses(&pool, &reactions, posts, user).await?;
+    let visibility = Visibility::Private;
+    let page = Page::new(response, pagination.limit, pagination.offset);
+    
+    Ok((visibility, VARY_USER, Json(page)))
+}
+
+async fn get_post(
+    State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    MaybeUser(user): MaybeUser,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let post = PostRepository::new(pool.clone()).find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Post with id {} not found", id)))?;
+    
+    let visibility = if post.published && user.is_none() { Visibility::Public } else { Visibility::Private };
+    
+    let (mut response, last_modified) = build_responses(&pool, &reactions, vec![post], user).await?;
+    
+    Ok((last_modified.map(LastModified), visibility, VARY_USER, Json(response.remove(0))))
+}
+
+async fn create_post(
+    State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    State(hooks): State<PostHooks>,
+    Query(params): Query<AuthorParam>,
+    Json(payload): Json<CreatePostRequest>,
+) -> Result<Json<PostResponse>> {
+    let post = posts::create(&pool, &hooks, params.author_id, &payload).await?;
+    
+    let (mut response, _) = build_responses(&pool, &reactions, vec![post], None).await?;
+    Ok(Json(response.remove(0)))
+}
+
+async fn update_post(
+    State(pool): State<PgPool>,
+    State(reactions): State<Arc<ReactionsConfig>>,
+    State(hooks): State<PostHooks>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdatePostRequest>,
+) -> Result<Json<PostResponse>> {
+    let post = posts::update(&pool, &hooks, id, &payload).await?;
+    
+    let (mut response, _) = build_responses(&pool, &reactions, vec![post], None).await?;
+    Ok(Json(response.remove(0)))
+}
+
+// Asks for one more post than the page holds, to learn whether there is a next page
+fn overfetch(pagination: &Pagination) -> Pagination {
+    Pagination {
+        limit: pagination.limit.saturating_add(1),
+        ..pagination.clone()
+    }
+}
+
+// The v1 responses with the authors embedded
+async fn build_responses(
+    pool: &PgPool,
+    reactions: &ReactionsConfig,
+    posts: Vec<Post>,
+    user: Option<CurrentUser>,
+) -> Result<(Vec<PostResponse>, Option<DateTime<Utc>>)> {
+    let mut author_ids: Vec<Uuid> = posts.iter().map(|p| p.author_id).collect();
+    author_ids.sort_unstable();
+    author_ids.dedup();
+    let authors: HashMap<Uuid, Author> = UserRepository::new(pool.clone())
+        .find_by_ids(&author_ids)
+        .await?
+        .into_iter()
+        .map(|user| (user.id, Author::from(user)))
+        .collect();
+    
+    let (responses, last_modified) = posts::build_responses(pool, reactions, posts, user).await?;
+    let responses = responses
+        .into_iter()
+        .map(|post| {
+            // Deleting a user deletes their posts, so this only fails when racing with that
+            let author = authors.get(&post.author_id).cloned()
+                .ok_or_else(|| AppError::NotFoundError(format!("User with id {} not found", post.author_id)))?;
+            Ok(PostResponse::new(post, author))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    
+    Ok((responses, last_modified))
+}
\ No newline at end of file
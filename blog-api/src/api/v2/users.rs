@@ -0,0 +1,33 @@
+
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Json, Router,
+};
+use sqlx::PgPool;
+
+use crate::api::users::{self, Pagination, UserState};
+use crate::db::UserRepository;
+use crate::errors::Result;
+use crate::middleware::RateLimiter;
+use crate::models::user::UserResponse;
+use crate::models::v2::Page;
+
+pub fn create_router(state: UserState, limiter: &RateLimiter) -> Router {
+    Router::new()
+        .route("/", get(list_users))
+        .merge(users::common_routes(limiter))
+        .with_state(state)
+}
+
+async fn list_users(
+    State(pool): State<PgPool>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Page<UserResponse>>> {
+    let repo = UserRepository::new(pool.clone());
+    // One extra user tells whether there is a next page
+    let users = repo.list(pagination.limit.saturating_add(1), pagination.offset).await?;
+    
+    let response = users::with_follow_counts(&pool, users).await?;
+    Ok(Json(Page::new(response, pagination.limit, pagination.offset)))
+}
\ No newline at end of file
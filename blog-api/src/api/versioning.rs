@@ -0,0 +1,68 @@
+use std::convert::Infallible;
+use std::time::SystemTime;
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    middleware,
+    response::Response,
+    Router,
+};
+use tower::{service_fn, util::BoxCloneService, ServiceExt};
+
+use crate::config::ApiVersionsConfig;
+
+// Media type that selects v2 on the unversioned /api paths
+pub const V2_MEDIA_TYPE: &str = "application/vnd.blog.v2+json";
+
+// Serves the unversioned /api paths, which predate versioning: v2 when the Accept header
+// asks for it, v1 otherwise. An explicit /api/v1 or /api/v2 path always wins.
+pub fn dispatch(v1: Router, v2: Router) -> BoxCloneService<Request, Response, Infallible> {
+    BoxCloneService::new(service_fn(move |request: Request| {
+        let router = if accepts(request.headers(), V2_MEDIA_TYPE) { v2.clone() } else { v1.clone() };
+        async move {
+            let mut response = router.oneshot(request).await?;
+            response.headers_mut().append(header::VARY, HeaderValue::from_static("Accept"));
+            Ok(response)
+        }
+    }))
+}
+
+fn accepts(headers: &HeaderMap, media_type: &str) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|range| range.split(';').next().is_some_and(|t| t.trim().eq_ignore_ascii_case(media_type)))
+}
+
+#[derive(Clone)]
+struct DeprecationHeaders {
+    deprecation: HeaderValue,
+    sunset: Option<HeaderValue>,
+}
+
+// Announces v1's deprecation (RFC 9745) on all of its responses, along with its planned
+// removal (RFC 8594) once a date has been set
+pub fn deprecate(router: Router, config: &ApiVersionsConfig) -> Router {
+    let headers = DeprecationHeaders {
+        deprecation: HeaderValue::from_str(&format!("@{}", config.v1_deprecated_at.timestamp()))
+            .expect("a timestamp is a valid header value"),
+        sunset: config.v1_sunset_at.map(|at| {
+            HeaderValue::from_str(&httpdate::fmt_http_date(SystemTime::from(at)))
+                .expect("an HTTP date is a valid header value")
+        }),
+    };
+
+    router.layer(middleware::map_response_with_state(headers, add_deprecation_headers))
+}
+
+async fn add_deprecation_headers(State(headers): State<DeprecationHeaders>, mut response: Response) -> Response {
+    let response_headers = response.headers_mut();
+    response_headers.insert(HeaderName::from_static("deprecation"), headers.deprecation);
+    if let Some(sunset) = headers.sunset {
+        response_headers.insert(HeaderName::from_static("sunset"), sunset);
+    }
+    response
+}
\ No newline at end of file
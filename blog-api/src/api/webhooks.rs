@@ -0,0 +1,176 @@
+use axum::{
+    extract::{FromRef, Path, Query, State},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use super::auth::AdminUser;
+use crate::db::WebhookRepository;
+use crate::errors::{AppError, Result};
+use crate::models::webhook::{
+    CreateWebhookRequest, CreatedWebhookResponse, UpdateWebhookRequest, WebhookDelivery, WebhookDeliveryResponse,
+    WebhookSubscription, EVENTS,
+};
+use crate::webhooks::{self, Webhooks};
+
+#[derive(Clone, FromRef)]
+struct WebhookState {
+    pool: PgPool,
+    webhooks: Webhooks,
+}
+
+pub fn create_router(pool: PgPool, webhooks: Webhooks) -> Router {
+    Router::new()
+        .route("/", get(list_webhooks).post(create_webhook))
+        .route("/:id", get(get_webhook).put(update_webhook).delete(delete_webhook))
+        .route("/:id/ping", post(ping_webhook))
+        .route("/:id/deliveries", get(list_deliveries))
+        .route("/:id/deliveries/:delivery_id", get(get_delivery))
+        .route("/:id/deliveries/:delivery_id/redeliver", post(redeliver))
+        .with_state(WebhookState { pool, webhooks })
+}
+
+#[derive(Debug, Deserialize)]
+struct Pagination {
+    #[serde(default = "default_limit")]
+    limit: i64,
+    #[serde(default)]
+    offset: i64,
+}
+
+fn default_limit() -> i64 {
+    20
+}
+
+fn validate_events(events: &[String]) -> Result<()> {
+    match events.iter().find(|e| !EVENTS.contains(&e.as_str())) {
+        Some(event) => Err(AppError::BadRequest(format!("Unknown webhook event: {}", event))),
+        None => Ok(()),
+    }
+}
+
+async fn list_webhooks(_admin: AdminUser, State(pool): State<PgPool>) -> Result<Json<Vec<WebhookSubscription>>> {
+    let subscriptions = WebhookRepository::new(pool).list().await?;
+    Ok(Json(subscriptions))
+}
+
+async fn create_webhook(
+    _admin: AdminUser,
+    State(pool): State<PgPool>,
+    State(webhooks): State<Webhooks>,
+    Json(payload): Json<CreateWebhookRequest>,
+) -> Result<Json<CreatedWebhookResponse>> {
+    payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
+    validate_events(&payload.events)?;
+    webhooks.check_url(&payload.url)?;
+    
+    let secret = payload.secret.clone().unwrap_or_else(webhooks::generate_secret);
+    let subscription = WebhookRepository::new(pool).create(&payload.url, &payload.events, &secret).await?;
+    
+    Ok(Json(CreatedWebhookResponse { subscription, secret }))
+}
+
+async fn get_webhook(
+    _admin: AdminUser,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WebhookSubscription>> {
+    let subscription = WebhookRepository::new(pool).find_by_id(id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Webhook with id {} not found", id)))?;
+    
+    Ok(Json(subscription))
+}
+
+async fn update_webhook(
+    _admin: AdminUser,
+    State(pool): State<PgPool>,
+    State(webhooks): State<Webhooks>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateWebhookRequest>,
+) -> Result<Json<WebhookSubscription>> {
+    payload.validate().map_err(|e| AppError::ValidationError(e.to_string()))?;
+    if let Some(events) = &payload.events {
+        validate_events(events)?;
+    }
+    if let Some(url) = &payload.url {
+        webhooks.check_url(url)?;
+    }
+    
+    let subscription = WebhookRepository::new(pool).update(id, &payload).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Webhook with id {} not found", id)))?;
+    
+    Ok(Json(subscription))
+}
+
+async fn delete_webhook(
+    _admin: AdminUser,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<serde_json::Value>> {
+    let deleted = WebhookRepository::new(pool).delete(id).await?;
+    
+    if !deleted {
+        return Err(AppError::NotFoundError(format!("Webhook with id {} not found", id)));
+    }
+    
+    Ok(Json(serde_json::json!({ "message": "Webhook deleted successfully" })))
+}
+
+async fn ping_webhook(
+    _admin: AdminUser,
+    State(pool): State<PgPool>,
+    State(webhooks): State<Webhooks>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<WebhookDelivery>> {
+    if WebhookRepository::new(pool).find_by_id(id).await?.is_none() {
+        return Err(AppError::NotFoundError(format!("Webhook with id {} not found", id)));
+    }
+    
+    let delivery = webhooks.ping(id).await?;
+    Ok(Json(delivery))
+}
+
+async fn list_deliveries(
+    _admin: AdminUser,
+    State(pool): State<PgPool>,
+    Path(id): Path<Uuid>,
+    Query(pagination): Query<Pagination>,
+) -> Result<Json<Vec<WebhookDelivery>>> {
+    let repo = WebhookRepository::new(pool);
+    if repo.find_by_id(id).await?.is_none() {
+        return Err(AppError::NotFoundError(format!("Webhook with id {} not found", id)));
+    }
+    
+    let deliveries = repo.list_deliveries(id, pagination.limit, pagination.offset).await?;
+    Ok(Json(deliveries))
+}
+
+async fn get_delivery(
+    _admin: AdminUser,
+    State(pool): State<PgPool>,
+    Path((id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<WebhookDeliveryResponse>> {
+    let repo = WebhookRepository::new(pool);
+    let delivery = repo.find_delivery(id, delivery_id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Delivery with id {} not found", delivery_id)))?;
+    let attempt_log = repo.find_attempts(delivery.id).await?;
+    
+    Ok(Json(WebhookDeliveryResponse { delivery, attempt_log }))
+}
+
+async fn redeliver(
+    _admin: AdminUser,
+    State(pool): State<PgPool>,
+    State(webhooks): State<Webhooks>,
+    Path((id, delivery_id)): Path<(Uuid, Uuid)>,
+) -> Result<Json<WebhookDelivery>> {
+    let delivery = WebhookRepository::new(pool).redeliver(id, delivery_id).await?
+        .ok_or_else(|| AppError::NotFoundError(format!("Delivery with id {} not found", delivery_id)))?;
+    webhooks.wake();
+    
+    Ok(Json(delivery))
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use sqlx::PgPool;
+
+use crate::errors::{AppError, Result};
+use crate::models::event::PostEventData;
+
+pub struct EventRepository {
+    pool: PgPool,
+}
+
+impl EventRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // Sends the event to every instance listening on `channel`, with the next live event id
+    pub async fn notify(&self, channel: &str, event: &str, data: &PostEventData) -> Result<()> {
+        let data = serde_json::to_value(data).map_err(|e| anyhow::anyhow!(e))?;
+
+        sqlx::query(
+            r#"
+            SELECT pg_notify(
+                $1,
+                json_build_object('id', nextval('live_event_id_seq'), 'event', $2::text, 'data', $3::jsonb)::text
+            )
+            "#,
+        )
+        .bind(channel)
+        .bind(event)
+        .bind(data)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
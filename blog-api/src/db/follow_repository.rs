@@ -0,0 +1,61 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::{AppError, Result};
+use crate::models::follow::FollowCounts;
+
+pub struct FollowRepository {
+    pool: PgPool,
+}
+
+impl FollowRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // Returns false when the user was already following
+    pub async fn follow(&self, follower_id: Uuid, followee_id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO follows (follower_id, followee_id)
+            VALUES ($1, $2)
+            ON CONFLICT (follower_id, followee_id) DO NOTHING
+            "#,
+        )
+        .bind(follower_id)
+        .bind(followee_id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn unfollow(&self, follower_id: Uuid, followee_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM follows WHERE follower_id = $1 AND followee_id = $2")
+            .bind(follower_id)
+            .bind(followee_id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn counts(&self, user_ids: &[Uuid]) -> Result<Vec<FollowCounts>> {
+        let counts = sqlx::query_as::<_, FollowCounts>(
+            r#"
+            SELECT u.id AS user_id,
+                   (SELECT COUNT(*) FROM follows f WHERE f.followee_id = u.id) AS follower_count,
+                   (SELECT COUNT(*) FROM follows f WHERE f.follower_id = u.id) AS following_count
+            FROM UNNEST($1::uuid[]) AS u(id)
+            "#,
+        )
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(counts)
+    }
+}
\ No newline at end of file
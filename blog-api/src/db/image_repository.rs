@@ -0,0 +1,221 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::{AppError, Result};
+use crate::models::image::{Image, ImageVariant, STATUS_FAILED, STATUS_PENDING, STATUS_PROCESSING, STATUS_READY};
+
+pub struct ImageRepository {
+    pool: PgPool,
+}
+
+impl ImageRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, id: Uuid, uploader_id: Uuid) -> Result<Image> {
+        let image = sqlx::query_as::<_, Image>(
+            r#"
+            INSERT INTO images (id, uploader_id, status)
+            VALUES ($1, $2, $3)
+            RETURNING id, uploader_id, status, width, height, error, created_at, updated_at
+            "#,
+        )
+        .bind(id)
+        .bind(uploader_id)
+        .bind(STATUS_PENDING)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(image)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Image>> {
+        let image = sqlx::query_as::<_, Image>(
+            r#"
+            SELECT id, uploader_id, status, width, height, error, created_at, updated_at
+            FROM images
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(image)
+    }
+
+    // Puts images that were mid-processing when the server last stopped back in line
+    pub async fn reset_processing(&self) -> Result<u64> {
+        let result = sqlx::query("UPDATE images SET status = $1, updated_at = NOW() WHERE status = $2")
+            .bind(STATUS_PENDING)
+            .bind(STATUS_PROCESSING)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn find_pending(&self) -> Result<Vec<Uuid>> {
+        let ids = sqlx::query_scalar::<_, Uuid>("SELECT id FROM images WHERE status = $1 ORDER BY created_at")
+            .bind(STATUS_PENDING)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(ids)
+    }
+
+    // Moves a pending image to processing. Returns false when it was not pending, e.g.
+    // because a sweep queued it a second time and another worker got there first.
+    pub async fn claim(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("UPDATE images SET status = $1, updated_at = NOW() WHERE id = $2 AND status = $3")
+            .bind(STATUS_PROCESSING)
+            .bind(id)
+            .bind(STATUS_PENDING)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn mark_failed(&self, id: Uuid, error: &str) -> Result<()> {
+        self.set_status(id, STATUS_FAILED, Some(error)).await
+    }
+
+    async fn set_status(&self, id: Uuid, status: &str, error: Option<&str>) -> Result<()> {
+        sqlx::query("UPDATE images SET status = $1, error = $2, updated_at = NOW() WHERE id = $3")
+            .bind(status)
+            .bind(error)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(())
+    }
+
+    // Records the variants and the original dimensions in one transaction
+    pub async fn mark_ready(&self, id: Uuid, width: i32, height: i32, variants: &[ImageVariant]) -> Result<()> {
+        let mut tx = self.pool.begin().await.map_err(AppError::DatabaseError)?;
+
+        for variant in variants {
+            sqlx::query(
+                r#"
+                INSERT INTO image_variants (image_id, name, format, width, height, size_bytes, storage_key)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (image_id, name, format) DO UPDATE
+                SET width = EXCLUDED.width, height = EXCLUDED.height,
+                    size_bytes = EXCLUDED.size_bytes, storage_key = EXCLUDED.storage_key
+                "#,
+            )
+            .bind(variant.image_id)
+            .bind(&variant.name)
+            .bind(&variant.format)
+            .bind(variant.width)
+            .bind(variant.height)
+            .bind(variant.size_bytes)
+            .bind(&variant.storage_key)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::DatabaseError)?;
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE images
+            SET status = $1, width = $2, height = $3, error = NULL, updated_at = NOW()
+            WHERE id = $4
+            "#,
+        )
+        .bind(STATUS_READY)
+        .bind(width)
+        .bind(height)
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        tx.commit().await.map_err(AppError::DatabaseError)?;
+
+        Ok(())
+    }
+
+    pub async fn find_variants(&self, image_id: Uuid) -> Result<Vec<ImageVariant>> {
+        let variants = sqlx::query_as::<_, ImageVariant>(
+            r#"
+            SELECT image_id, name, format, width, height, size_bytes, storage_key
+            FROM image_variants
+            WHERE image_id = $1
+            ORDER BY width, format
+            "#,
+        )
+        .bind(image_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(variants)
+    }
+
+    pub async fn find_variant(&self, image_id: Uuid, name: &str, format: &str) -> Result<Option<ImageVariant>> {
+        let variant = sqlx::query_as::<_, ImageVariant>(
+            r#"
+            SELECT image_id, name, format, width, height, size_bytes, storage_key
+            FROM image_variants
+            WHERE image_id = $1 AND name = $2 AND format = $3
+            "#,
+        )
+        .bind(image_id)
+        .bind(name)
+        .bind(format)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(variant)
+    }
+
+    // Returns the ids of the user's images, used to clean up stored files
+    pub async fn find_ids_by_uploader(&self, uploader_id: Uuid) -> Result<Vec<Uuid>> {
+        let ids = sqlx::query_scalar::<_, Uuid>("SELECT id FROM images WHERE uploader_id = $1")
+            .bind(uploader_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(ids)
+    }
+
+    // Every variant of every image the user uploaded, in one query
+    pub async fn find_variants_by_uploader(&self, uploader_id: Uuid) -> Result<Vec<ImageVariant>> {
+        let variants = sqlx::query_as::<_, ImageVariant>(
+            r#"
+            SELECT v.image_id, v.name, v.format, v.width, v.height, v.size_bytes, v.storage_key
+            FROM image_variants v
+            JOIN images i ON i.id = v.image_id
+            WHERE i.uploader_id = $1
+            "#,
+        )
+        .bind(uploader_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(variants)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM images WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
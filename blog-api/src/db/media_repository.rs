@@ -0,0 +1,94 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::{AppError, Result};
+use crate::models::media::Media;
+
+pub struct MediaRepository {
+    pool: PgPool,
+}
+
+pub struct NewMedia<'a> {
+    pub id: Uuid,
+    pub uploader_id: Uuid,
+    pub file_name: &'a str,
+    pub content_type: &'a str,
+    pub size_bytes: i64,
+    pub storage_key: &'a str,
+}
+
+impl MediaRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, media: &NewMedia<'_>) -> Result<Media> {
+        let media = sqlx::query_as::<_, Media>(
+            r#"
+            INSERT INTO media (id, uploader_id, file_name, content_type, size_bytes, storage_key)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, uploader_id, file_name, content_type, size_bytes, storage_key, created_at
+            "#,
+        )
+        .bind(media.id)
+        .bind(media.uploader_id)
+        .bind(media.file_name)
+        .bind(media.content_type)
+        .bind(media.size_bytes)
+        .bind(media.storage_key)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(media)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Media>> {
+        let media = sqlx::query_as::<_, Media>(
+            r#"
+            SELECT id, uploader_id, file_name, content_type, size_bytes, storage_key, created_at
+            FROM media
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(media)
+    }
+
+    pub async fn find_by_uploader(&self, uploader_id: Uuid) -> Result<Vec<Media>> {
+        let media = sqlx::query_as::<_, Media>(
+            r#"
+            SELECT id, uploader_id, file_name, content_type, size_bytes, storage_key, created_at
+            FROM media
+            WHERE uploader_id = $1
+            "#,
+        )
+        .bind(uploader_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(media)
+    }
+
+    // Returns the deleted row so the caller can remove the stored file
+    pub async fn delete(&self, id: Uuid) -> Result<Option<Media>> {
+        let media = sqlx::query_as::<_, Media>(
+            r#"
+            DELETE FROM media
+            WHERE id = $1
+            RETURNING id, uploader_id, file_name, content_type, size_bytes, storage_key, created_at
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(media)
+    }
+}
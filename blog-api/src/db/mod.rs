@@ -1,7 +1,17 @@
 pub mod user_repository;
 pub mod post_repository;
+pub mod media_repository;
+pub mod image_repository;
+pub mod reaction_repository;
+pub mod read_repository;
+pub mod reading_list_repository;
+pub mod follow_repository;
+pub mod notification_repository;
+pub mod webhook_repository;
+pub mod event_repository;
 
 use crate::config::DatabaseConfig;
+use sqlx::migrate::Migrator;
 use sqlx::postgres::{PgPool, PgPoolOptions};
 use std::time::Duration;
 
@@ -15,11 +25,40 @@ pub async fn create_pool(config: &DatabaseConfig) -> Result<PgPool, sqlx::Error>
     Ok(pool)
 }
 
+// Attributes shared by the repository query spans, after the OpenTelemetry database conventions
+const SPAN_KIND: &str = "client";
+const DB_SYSTEM: &str = "postgresql";
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
 pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::Error> {
-    sqlx::migrate!("./migrations").run(pool).await?;
+    MIGRATOR.run(pool).await?;
     Ok(())
 }
 
+// Returns (applied, expected) counts of the migrations embedded in this build
+pub async fn migration_status(pool: &PgPool) -> Result<(usize, usize), sqlx::Error> {
+    let applied: Vec<i64> = sqlx::query_scalar("SELECT version FROM _sqlx_migrations WHERE success")
+        .fetch_all(pool)
+        .await?;
+
+    let expected = MIGRATOR.iter().filter(|m| !m.migration_type.is_down_migration()).count();
+    let applied = MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration() && applied.contains(&m.version))
+        .count();
+    Ok((applied, expected))
+}
+
 // Re-export repositories for convenience
+pub use event_repository::EventRepository;
+pub use follow_repository::FollowRepository;
+pub use image_repository::ImageRepository;
+pub use media_repository::MediaRepository;
+pub use notification_repository::NotificationRepository;
 pub use post_repository::PostRepository;
-pub use user_repository::UserRepository;
\ No newline at end of file
+pub use reaction_repository::ReactionRepository;
+pub use read_repository::ReadRepository;
+pub use reading_list_repository::ReadingListRepository;
+pub use user_repository::UserRepository;
+pub use webhook_repository::WebhookRepository;
\ No newline at end of file
@@ -0,0 +1,140 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::{AppError, Result};
+use crate::models::notification::Notification;
+
+pub struct NotificationRepository {
+    pool: PgPool,
+}
+
+// Identifies one event for `NotificationRepository::record`
+pub struct NewNotification<'a> {
+    pub recipient_id: Uuid,
+    pub kind: &'a str,
+    pub post_id: Option<Uuid>,
+    pub group_key: String,
+    pub actor_id: Uuid,
+}
+
+impl NotificationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // Folds the event into the recipient's unread notification for the same group, or starts
+    // a new one. Nothing is stored when the recipient has muted this kind.
+    pub async fn record(&self, notification: &NewNotification<'_>) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO notifications (recipient_id, kind, post_id, group_key, actor_ids)
+            SELECT $1, $2, $3, $4, ARRAY[$5]::uuid[]
+            WHERE NOT EXISTS (
+                SELECT 1 FROM notification_preferences
+                WHERE user_id = $1 AND kind = $2 AND muted = true
+            )
+            ON CONFLICT (recipient_id, group_key) WHERE read_at IS NULL
+            DO UPDATE SET actor_ids = array_prepend($5, array_remove(notifications.actor_ids, $5)),
+                          updated_at = NOW()
+            "#,
+        )
+        .bind(notification.recipient_id)
+        .bind(notification.kind)
+        .bind(notification.post_id)
+        .bind(&notification.group_key)
+        .bind(notification.actor_id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn list(&self, recipient_id: Uuid, unread_only: bool, limit: i64, offset: i64) -> Result<Vec<Notification>> {
+        let notifications = sqlx::query_as::<_, Notification>(
+            r#"
+            SELECT id, recipient_id, kind, post_id, group_key, actor_ids, read_at, created_at, updated_at
+            FROM notifications
+            WHERE recipient_id = $1 AND (read_at IS NULL OR NOT $2)
+            ORDER BY updated_at DESC
+            LIMIT $3 OFFSET $4
+            "#,
+        )
+        .bind(recipient_id)
+        .bind(unread_only)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(notifications)
+    }
+
+    pub async fn count_unread(&self, recipient_id: Uuid) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM notifications WHERE recipient_id = $1 AND read_at IS NULL")
+            .bind(recipient_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(count)
+    }
+
+    // Returns false when the notification doesn't exist or belongs to someone else
+    pub async fn mark_read(&self, recipient_id: Uuid, id: Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE notifications
+            SET read_at = COALESCE(read_at, NOW())
+            WHERE id = $1 AND recipient_id = $2
+            "#,
+        )
+        .bind(id)
+        .bind(recipient_id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn mark_all_read(&self, recipient_id: Uuid) -> Result<u64> {
+        let result = sqlx::query("UPDATE notifications SET read_at = NOW() WHERE recipient_id = $1 AND read_at IS NULL")
+            .bind(recipient_id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected())
+    }
+
+    // Returns the kinds the user has muted
+    pub async fn muted_kinds(&self, user_id: Uuid) -> Result<Vec<String>> {
+        let kinds = sqlx::query_scalar("SELECT kind FROM notification_preferences WHERE user_id = $1 AND muted = true")
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(kinds)
+    }
+
+    pub async fn set_muted(&self, user_id: Uuid, kind: &str, muted: bool) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO notification_preferences (user_id, kind, muted)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, kind) DO UPDATE SET muted = EXCLUDED.muted
+            "#,
+        )
+        .bind(user_id)
+        .bind(kind)
+        .bind(muted)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -1,6 +1,9 @@
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
+use tracing::instrument;
 use uuid::Uuid;
 
+use crate::db::{DB_SYSTEM, SPAN_KIND};
 use crate::errors::{AppError, Result};
 use crate::models::post::{CreatePostRequest, Post, UpdatePostRequest};
 
@@ -13,6 +16,7 @@ impl PostRepository {
         Self { pool }
     }
 
+    #[instrument(name = "INSERT posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "INSERT posts"))]
     pub async fn create(&self, post: &CreatePostRequest, author_id: Uuid) -> Result<Post> {
         let published = post.published.unwrap_or(false);
 
@@ -34,6 +38,7 @@ impl PostRepository {
         Ok(post)
     }
 
+    #[instrument(name = "SELECT posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT posts"))]
     pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Post>> {
         let post = sqlx::query_as::<_, Post>(
             r#"
@@ -50,6 +55,24 @@ impl PostRepository {
         Ok(post)
     }
 
+    #[instrument(name = "SELECT posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT posts"))]
+    pub async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<Post>> {
+        let posts = sqlx::query_as::<_, Post>(
+            r#"
+            SELECT id, title, content, author_id, published, created_at, updated_at
+            FROM posts
+            WHERE id = ANY($1)
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(posts)
+    }
+
+    #[instrument(name = "UPDATE posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "UPDATE posts"))]
     pub async fn update(&self, id: Uuid, post: &UpdatePostRequest) -> Result<Option<Post>> {
         // Check if the post exists and get current values
         let existing = self.find_by_id(id).await?;
@@ -83,6 +106,7 @@ impl PostRepository {
         Ok(Some(updated_post))
     }
 
+    #[instrument(name = "DELETE posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "DELETE posts"))]
     pub async fn delete(&self, id: Uuid) -> Result<bool> {
         let result = sqlx::query("DELETE FROM posts WHERE id = $1")
             .bind(id)
@@ -93,6 +117,7 @@ impl PostRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    #[instrument(name = "SELECT posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT posts"))]
     pub async fn list(&self, limit: i64, offset: i64, published_only: bool) -> Result<Vec<Post>> {
         let query = if published_only {
             r#"
@@ -121,23 +146,187 @@ impl PostRepository {
         Ok(posts)
     }
 
-    pub async fn find_by_author(&self, author_id: Uuid, limit: i64, offset: i64) -> Result<Vec<Post>> {
-        let posts = sqlx::query_as::<_, Post>(
+    // Like `list`, but skips posts the user has already read
+    #[instrument(name = "SELECT posts post_reads", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT posts post_reads"))]
+    pub async fn list_unread(&self, user_id: Uuid, limit: i64, offset: i64, published_only: bool) -> Result<Vec<Post>> {
+        let query = if published_only {
+            r#"
+            SELECT id, title, content, author_id, published, created_at, updated_at
+            FROM posts p
+            WHERE published = true
+              AND NOT EXISTS (SELECT 1 FROM post_reads r WHERE r.user_id = $1 AND r.post_id = p.id)
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#
+        } else {
+            r#"
+            SELECT id, title, content, author_id, published, created_at, updated_at
+            FROM posts p
+            WHERE NOT EXISTS (SELECT 1 FROM post_reads r WHERE r.user_id = $1 AND r.post_id = p.id)
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#
+        };
+
+        let posts = sqlx::query_as::<_, Post>(query)
+            .bind(user_id)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(posts)
+    }
+
+    #[instrument(name = "SELECT posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT posts"))]
+    pub async fn find_by_author(&self, author_id: Uuid, limit: i64, offset: i64, published_only: bool) -> Result<Vec<Post>> {
+        let query = if published_only {
+            r#"
+            SELECT id, title, content, author_id, published, created_at, updated_at
+            FROM posts
+            WHERE author_id = $1 AND published = true
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#
+        } else {
             r#"
             SELECT id, title, content, author_id, published, created_at, updated_at
             FROM posts
             WHERE author_id = $1
             ORDER BY created_at DESC
             LIMIT $2 OFFSET $3
+            "#
+        };
+
+        let posts = sqlx::query_as::<_, Post>(query)
+            .bind(author_id)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(posts)
+    }
+
+    // The latest `limit` posts of each of several authors in one query, newest first per author
+    #[instrument(name = "SELECT posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT posts"))]
+    pub async fn find_by_authors(&self, author_ids: &[Uuid], limit: i64, published_only: bool) -> Result<Vec<Post>> {
+        let query = if published_only {
+            r#"
+            SELECT p.id, p.title, p.content, p.author_id, p.published, p.created_at, p.updated_at
+            FROM UNNEST($1::uuid[]) AS a(id)
+            CROSS JOIN LATERAL (
+                SELECT id, title, content, author_id, published, created_at, updated_at
+                FROM posts
+                WHERE author_id = a.id AND published = true
+                ORDER BY created_at DESC
+                LIMIT $2
+            ) p
+            "#
+        } else {
+            r#"
+            SELECT p.id, p.title, p.content, p.author_id, p.published, p.created_at, p.updated_at
+            FROM UNNEST($1::uuid[]) AS a(id)
+            CROSS JOIN LATERAL (
+                SELECT id, title, content, author_id, published, created_at, updated_at
+                FROM posts
+                WHERE author_id = a.id
+                ORDER BY created_at DESC
+                LIMIT $2
+            ) p
+            "#
+        };
+
+        let posts = sqlx::query_as::<_, Post>(query)
+            .bind(author_ids)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(posts)
+    }
+
+    // Latest published posts by the authors a user follows, newest first, starting after the
+    // (created_at, id) cursor. Each author's posts are read from their own index range and then
+    // merged, so the cost grows with the number of followed authors rather than the post table.
+    #[instrument(name = "SELECT follows posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT follows posts"))]
+    pub async fn find_followed(&self, follower_id: Uuid, before: Option<(DateTime<Utc>, Uuid)>, limit: i64) -> Result<Vec<Post>> {
+        let query = if before.is_some() {
+            r#"
+            SELECT p.id, p.title, p.content, p.author_id, p.published, p.created_at, p.updated_at
+            FROM follows f
+            CROSS JOIN LATERAL (
+                SELECT id, title, content, author_id, published, created_at, updated_at
+                FROM posts
+                WHERE author_id = f.followee_id AND published = true AND (created_at, id) < ($3, $4)
+                ORDER BY created_at DESC, id DESC
+                LIMIT $2
+            ) p
+            WHERE f.follower_id = $1
+            ORDER BY p.created_at DESC, p.id DESC
+            LIMIT $2
+            "#
+        } else {
+            r#"
+            SELECT p.id, p.title, p.content, p.author_id, p.published, p.created_at, p.updated_at
+            FROM follows f
+            CROSS JOIN LATERAL (
+                SELECT id, title, content, author_id, published, created_at, updated_at
+                FROM posts
+                WHERE author_id = f.followee_id AND published = true
+                ORDER BY created_at DESC, id DESC
+                LIMIT $2
+            ) p
+            WHERE f.follower_id = $1
+            ORDER BY p.created_at DESC, p.id DESC
+            LIMIT $2
+            "#
+        };
+
+        let mut query = sqlx::query_as::<_, Post>(query).bind(follower_id).bind(limit);
+        if let Some((before_at, before_id)) = before {
+            query = query.bind(before_at).bind(before_id);
+        }
+
+        let posts = query
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(posts)
+    }
+
+    #[instrument(name = "SELECT posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT posts"))]
+    pub async fn count_published(&self) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM posts WHERE published = true")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(count)
+    }
+
+    // Returns (id, updated_at) for published posts in a stable order, for sitemaps
+    #[instrument(name = "SELECT posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT posts"))]
+    pub async fn list_published_timestamps(&self, limit: i64, offset: i64) -> Result<Vec<(Uuid, DateTime<Utc>)>> {
+        let rows = sqlx::query_as::<_, (Uuid, DateTime<Utc>)>(
+            r#"
+            SELECT id, updated_at
+            FROM posts
+            WHERE published = true
+            ORDER BY created_at, id
+            LIMIT $1 OFFSET $2
             "#,
         )
-        .bind(author_id)
         .bind(limit)
         .bind(offset)
         .fetch_all(&self.pool)
         .await
         .map_err(AppError::DatabaseError)?;
 
-        Ok(posts)
+        Ok(rows)
     }
 }
\ No newline at end of file
@@ -0,0 +1,81 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::{AppError, Result};
+use crate::models::reaction::ReactionCount;
+
+pub struct ReactionRepository {
+    pool: PgPool,
+}
+
+impl ReactionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // Returns false when the user had already left this reaction
+    pub async fn add(&self, post_id: Uuid, user_id: Uuid, kind: &str) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO post_reactions (post_id, user_id, kind)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (post_id, user_id, kind) DO NOTHING
+            "#,
+        )
+        .bind(post_id)
+        .bind(user_id)
+        .bind(kind)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn remove(&self, post_id: Uuid, user_id: Uuid, kind: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM post_reactions WHERE post_id = $1 AND user_id = $2 AND kind = $3")
+            .bind(post_id)
+            .bind(user_id)
+            .bind(kind)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // Reads the trigger-maintained totals, including kinds that dropped back to zero
+    pub async fn counts_for_posts(&self, post_ids: &[Uuid]) -> Result<Vec<ReactionCount>> {
+        let counts = sqlx::query_as::<_, ReactionCount>(
+            r#"
+            SELECT post_id, kind, count, updated_at
+            FROM post_reaction_counts
+            WHERE post_id = ANY($1)
+            "#,
+        )
+        .bind(post_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(counts)
+    }
+
+    // Returns (post_id, kind) for the reactions the user left on the given posts
+    pub async fn find_by_user(&self, user_id: Uuid, post_ids: &[Uuid]) -> Result<Vec<(Uuid, String)>> {
+        let rows = sqlx::query_as::<_, (Uuid, String)>(
+            r#"
+            SELECT post_id, kind
+            FROM post_reactions
+            WHERE user_id = $1 AND post_id = ANY($2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(post_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(rows)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::{AppError, Result};
+
+pub struct ReadRepository {
+    pool: PgPool,
+}
+
+impl ReadRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // Keeps the original read_at when the post was already read
+    pub async fn mark_read(&self, user_id: Uuid, post_id: Uuid) -> Result<DateTime<Utc>> {
+        let read_at: DateTime<Utc> = sqlx::query_scalar(
+            r#"
+            INSERT INTO post_reads (user_id, post_id)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id, post_id) DO UPDATE SET read_at = post_reads.read_at
+            RETURNING read_at
+            "#,
+        )
+        .bind(user_id)
+        .bind(post_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(read_at)
+    }
+
+    pub async fn mark_unread(&self, user_id: Uuid, post_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM post_reads WHERE user_id = $1 AND post_id = $2")
+            .bind(user_id)
+            .bind(post_id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // Ids of posts that no longer exist are skipped
+    pub async fn mark_many_read(&self, user_id: Uuid, post_ids: &[Uuid]) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO post_reads (user_id, post_id)
+            SELECT $1, id FROM posts WHERE id = ANY($2)
+            ON CONFLICT (user_id, post_id) DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .bind(post_ids)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn mark_all_read(&self, user_id: Uuid) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO post_reads (user_id, post_id)
+            SELECT $1, id FROM posts WHERE published = true
+            ON CONFLICT (user_id, post_id) DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected())
+    }
+
+    // Returns which of the given posts the user has read
+    pub async fn find_read(&self, user_id: Uuid, post_ids: &[Uuid]) -> Result<Vec<Uuid>> {
+        let ids = sqlx::query_scalar("SELECT post_id FROM post_reads WHERE user_id = $1 AND post_id = ANY($2)")
+            .bind(user_id)
+            .bind(post_ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(ids)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,242 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::errors::{AppError, Result};
+use crate::models::reading_list::{
+    CreateReadingListRequest, ReadingList, ReadingListItem, ReadingListSummary, UpdateReadingListRequest,
+};
+
+pub struct ReadingListRepository {
+    pool: PgPool,
+}
+
+impl ReadingListRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, owner_id: Uuid, list: &CreateReadingListRequest) -> Result<ReadingList> {
+        let list = sqlx::query_as::<_, ReadingList>(
+            r#"
+            INSERT INTO reading_lists (owner_id, name, description, shared)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, owner_id, name, description, shared, created_at, updated_at
+            "#,
+        )
+        .bind(owner_id)
+        .bind(&list.name)
+        .bind(list.description.as_deref().filter(|d| !d.is_empty()))
+        .bind(list.shared.unwrap_or(false))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(list)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<ReadingList>> {
+        let list = sqlx::query_as::<_, ReadingList>(
+            r#"
+            SELECT id, owner_id, name, description, shared, created_at, updated_at
+            FROM reading_lists
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(list)
+    }
+
+    pub async fn find_by_owner(&self, owner_id: Uuid) -> Result<Vec<ReadingListSummary>> {
+        let lists = sqlx::query_as::<_, ReadingListSummary>(
+            r#"
+            SELECT l.id, l.owner_id, l.name, l.description, l.shared, l.created_at, l.updated_at,
+                   (SELECT COUNT(*) FROM reading_list_items i WHERE i.list_id = l.id) AS item_count
+            FROM reading_lists l
+            WHERE l.owner_id = $1
+            ORDER BY l.created_at
+            "#,
+        )
+        .bind(owner_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(lists)
+    }
+
+    pub async fn update(&self, id: Uuid, list: &UpdateReadingListRequest) -> Result<Option<ReadingList>> {
+        let existing = match self.find_by_id(id).await? {
+            Some(existing) => existing,
+            None => return Ok(None),
+        };
+
+        // Update only the fields that are provided; an empty description clears it
+        let name = list.name.clone().unwrap_or(existing.name);
+        let description = match &list.description {
+            Some(d) if d.is_empty() => None,
+            Some(d) => Some(d.clone()),
+            None => existing.description,
+        };
+        let shared = list.shared.unwrap_or(existing.shared);
+
+        let updated = sqlx::query_as::<_, ReadingList>(
+            r#"
+            UPDATE reading_lists
+            SET name = $1, description = $2, shared = $3, updated_at = NOW()
+            WHERE id = $4
+            RETURNING id, owner_id, name, description, shared, created_at, updated_at
+            "#,
+        )
+        .bind(&name)
+        .bind(&description)
+        .bind(shared)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(updated)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM reading_lists WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // Items in list order. With `published_only`, drafts and unpublished posts are left out;
+    // deleted posts are already gone through the foreign key cascade.
+    pub async fn items(&self, list_id: Uuid, published_only: bool) -> Result<Vec<ReadingListItem>> {
+        let items = sqlx::query_as::<_, ReadingListItem>(
+            r#"
+            SELECT i.post_id, p.title, p.author_id, p.published, i.position, i.added_at
+            FROM reading_list_items i
+            JOIN posts p ON p.id = i.post_id
+            WHERE i.list_id = $1 AND (p.published OR NOT $2)
+            ORDER BY i.position, i.added_at
+            "#,
+        )
+        .bind(list_id)
+        .bind(published_only)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(items)
+    }
+
+    // Appends the post to the end of the list. Returns false when it was already in it.
+    pub async fn add_item(&self, list_id: Uuid, post_id: Uuid) -> Result<bool> {
+        let mut tx = self.pool.begin().await.map_err(AppError::DatabaseError)?;
+        lock(&mut tx, list_id).await?;
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO reading_list_items (list_id, post_id, position)
+            SELECT $1, $2, COALESCE(MAX(position) + 1, 0)
+            FROM reading_list_items
+            WHERE list_id = $1
+            ON CONFLICT (list_id, post_id) DO NOTHING
+            "#,
+        )
+        .bind(list_id)
+        .bind(post_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        let added = result.rows_affected() > 0;
+        if added {
+            touch(&mut tx, list_id).await?;
+        }
+        tx.commit().await.map_err(AppError::DatabaseError)?;
+
+        Ok(added)
+    }
+
+    pub async fn remove_item(&self, list_id: Uuid, post_id: Uuid) -> Result<bool> {
+        let mut tx = self.pool.begin().await.map_err(AppError::DatabaseError)?;
+
+        let result = sqlx::query("DELETE FROM reading_list_items WHERE list_id = $1 AND post_id = $2")
+            .bind(list_id)
+            .bind(post_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        let removed = result.rows_affected() > 0;
+        if removed {
+            touch(&mut tx, list_id).await?;
+        }
+        tx.commit().await.map_err(AppError::DatabaseError)?;
+
+        Ok(removed)
+    }
+
+    // Sets each item's position to its index in `post_ids`. Returns false, changing nothing,
+    // unless `post_ids` names every item in the list exactly once.
+    pub async fn reorder(&self, list_id: Uuid, post_ids: &[Uuid]) -> Result<bool> {
+        let mut tx = self.pool.begin().await.map_err(AppError::DatabaseError)?;
+        lock(&mut tx, list_id).await?;
+
+        let mut current = sqlx::query_scalar::<_, Uuid>("SELECT post_id FROM reading_list_items WHERE list_id = $1")
+            .bind(list_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(AppError::DatabaseError)?;
+        let mut requested = post_ids.to_vec();
+        current.sort_unstable();
+        requested.sort_unstable();
+        if current != requested {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE reading_list_items i
+            SET position = o.position - 1
+            FROM UNNEST($2::uuid[]) WITH ORDINALITY AS o(post_id, position)
+            WHERE i.list_id = $1 AND i.post_id = o.post_id
+            "#,
+        )
+        .bind(list_id)
+        .bind(post_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        touch(&mut tx, list_id).await?;
+        tx.commit().await.map_err(AppError::DatabaseError)?;
+
+        Ok(true)
+    }
+}
+
+// Serializes changes to a list's items, which all read or rewrite positions
+async fn lock(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, list_id: Uuid) -> Result<()> {
+    sqlx::query("SELECT id FROM reading_lists WHERE id = $1 FOR UPDATE")
+        .bind(list_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+    Ok(())
+}
+
+async fn touch(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, list_id: Uuid) -> Result<()> {
+    sqlx::query("UPDATE reading_lists SET updated_at = NOW() WHERE id = $1")
+        .bind(list_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+    Ok(())
+}
\ No newline at end of file
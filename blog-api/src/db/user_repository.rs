@@ -1,6 +1,9 @@
+use chrono::{DateTime, Utc};
 use sqlx::PgPool;
+use tracing::instrument;
 use uuid::Uuid;
 
+use crate::db::{DB_SYSTEM, SPAN_KIND};
 use crate::errors::{AppError, Result};
 use crate::models::user::{CreateUserRequest, UpdateUserRequest, User};
 
@@ -13,6 +16,7 @@ impl UserRepository {
         Self { pool }
     }
 
+    #[instrument(name = "INSERT users", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "INSERT users"))]
     pub async fn create(&self, user: &CreateUserRequest) -> Result<User> {
         // In a real application, you would hash the password here
         // For simplicity, we'll store it as plain text, but this is NOT secure
@@ -36,6 +40,7 @@ impl UserRepository {
         Ok(user)
     }
 
+    #[instrument(name = "SELECT users", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT users"))]
     pub async fn find_by_id(&self, id: Uuid) -> Result<Option<User>> {
         let user = sqlx::query_as::<_, User>(
             r#"
@@ -52,6 +57,53 @@ impl UserRepository {
         Ok(user)
     }
 
+    #[instrument(name = "SELECT users", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT users"))]
+    pub async fn is_admin(&self, id: Uuid) -> Result<bool> {
+        let is_admin = sqlx::query_scalar::<_, bool>("SELECT is_admin FROM users WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(is_admin.unwrap_or(false))
+    }
+
+    #[instrument(name = "SELECT users", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT users"))]
+    pub async fn find_by_ids(&self, ids: &[Uuid]) -> Result<Vec<User>> {
+        let users = sqlx::query_as::<_, User>(
+            r#"
+            SELECT id, username, email, password_hash, created_at, updated_at
+            FROM users
+            WHERE id = ANY($1)
+            "#,
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(users)
+    }
+
+    // Usernames are not unique, so every user with one of the names is returned
+    #[instrument(name = "SELECT users", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT users"))]
+    pub async fn find_by_usernames(&self, usernames: &[String]) -> Result<Vec<User>> {
+        let users = sqlx::query_as::<_, User>(
+            r#"
+            SELECT id, username, email, password_hash, created_at, updated_at
+            FROM users
+            WHERE username = ANY($1)
+            "#,
+        )
+        .bind(usernames)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(users)
+    }
+
+    #[instrument(name = "SELECT users", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT users"))]
     pub async fn find_by_email(&self, email: &str) -> Result<Option<User>> {
         let user = sqlx::query_as::<_, User>(
             r#"
@@ -68,6 +120,7 @@ impl UserRepository {
         Ok(user)
     }
 
+    #[instrument(name = "UPDATE users", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "UPDATE users"))]
     pub async fn update(&self, id: Uuid, user: &UpdateUserRequest) -> Result<Option<User>> {
         // Check if the user exists first
         let existing = self.find_by_id(id).await?;
@@ -101,6 +154,7 @@ impl UserRepository {
         Ok(Some(updated_user))
     }
 
+    #[instrument(name = "DELETE users", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "DELETE users"))]
     pub async fn delete(&self, id: Uuid) -> Result<bool> {
         let result = sqlx::query("DELETE FROM users WHERE id = $1")
             .bind(id)
@@ -111,6 +165,7 @@ impl UserRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    #[instrument(name = "SELECT users", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT users"))]
     pub async fn list(&self, limit: i64, offset: i64) -> Result<Vec<User>> {
         let users = sqlx::query_as::<_, User>(
             r#"
@@ -128,4 +183,38 @@ impl UserRepository {
 
         Ok(users)
     }
+
+    #[instrument(name = "SELECT posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT posts"))]
+    pub async fn count_with_published_posts(&self) -> Result<i64> {
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(DISTINCT author_id) FROM posts WHERE published = true",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(count)
+    }
+
+    // Returns (id, latest post update) for users with published posts, for sitemaps
+    #[instrument(name = "SELECT posts", skip_all, fields(otel.kind = SPAN_KIND, db.system.name = DB_SYSTEM, db.query.summary = "SELECT posts"))]
+    pub async fn list_with_published_posts(&self, limit: i64, offset: i64) -> Result<Vec<(Uuid, DateTime<Utc>)>> {
+        let rows = sqlx::query_as::<_, (Uuid, DateTime<Utc>)>(
+            r#"
+            SELECT author_id, MAX(updated_at)
+            FROM posts
+            WHERE published = true
+            GROUP BY author_id
+            ORDER BY author_id
+            LIMIT $1 OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(rows)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,320 @@
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::errors::{AppError, Result};
+use crate::models::webhook::{
+    UpdateWebhookRequest, WebhookDelivery, WebhookDeliveryAttempt, WebhookSubscription, STATUS_PENDING,
+};
+
+pub struct WebhookRepository {
+    pool: PgPool,
+}
+
+// A delivery claimed by the sender, with what it needs to make the request
+#[derive(Debug, FromRow)]
+pub struct DueDelivery {
+    pub id: Uuid,
+    pub event: String,
+    pub payload: serde_json::Value,
+    pub attempts: i32,
+    pub url: String,
+    pub secret: String,
+}
+
+pub struct DeliveryAttempt<'a> {
+    pub status_code: Option<i32>,
+    pub error: Option<&'a str>,
+    pub duration_ms: i32,
+}
+
+const DELIVERY_COLUMNS: &str = "id, subscription_id, event, payload, status, attempts, next_attempt_at, \
+    last_status_code, last_error, delivered_at, created_at, updated_at";
+
+impl WebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, url: &str, events: &[String], secret: &str) -> Result<WebhookSubscription> {
+        let subscription = sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            INSERT INTO webhook_subscriptions (url, events, secret)
+            VALUES ($1, $2, $3)
+            RETURNING id, url, events, active, created_at, updated_at
+            "#,
+        )
+        .bind(url)
+        .bind(events)
+        .bind(secret)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(subscription)
+    }
+
+    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<WebhookSubscription>> {
+        let subscription = sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            SELECT id, url, events, active, created_at, updated_at
+            FROM webhook_subscriptions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(subscription)
+    }
+
+    pub async fn list(&self) -> Result<Vec<WebhookSubscription>> {
+        let subscriptions = sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            SELECT id, url, events, active, created_at, updated_at
+            FROM webhook_subscriptions
+            ORDER BY created_at
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(subscriptions)
+    }
+
+    pub async fn update(&self, id: Uuid, update: &UpdateWebhookRequest) -> Result<Option<WebhookSubscription>> {
+        let existing = match self.find_by_id(id).await? {
+            Some(existing) => existing,
+            None => return Ok(None),
+        };
+
+        // Update only the fields that are provided
+        let url = update.url.clone().unwrap_or(existing.url);
+        let events = update.events.clone().unwrap_or(existing.events);
+        let active = update.active.unwrap_or(existing.active);
+
+        let subscription = sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            UPDATE webhook_subscriptions
+            SET url = $1, events = $2, active = $3, updated_at = NOW()
+            WHERE id = $4
+            RETURNING id, url, events, active, created_at, updated_at
+            "#,
+        )
+        .bind(&url)
+        .bind(&events)
+        .bind(active)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(subscription)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM webhook_subscriptions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // Queues the event for every active subscription that listens to it
+    pub async fn enqueue(&self, event: &str, payload: &serde_json::Value) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries (subscription_id, event, payload)
+            SELECT id, $1, $2
+            FROM webhook_subscriptions
+            WHERE active = true AND $1 = ANY(events)
+            "#,
+        )
+        .bind(event)
+        .bind(payload)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(result.rows_affected())
+    }
+
+    pub async fn enqueue_for(&self, subscription_id: Uuid, event: &str, payload: &serde_json::Value) -> Result<WebhookDelivery> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            r#"
+            INSERT INTO webhook_deliveries (subscription_id, event, payload)
+            VALUES ($1, $2, $3)
+            RETURNING {}
+            "#,
+            DELIVERY_COLUMNS
+        ))
+        .bind(subscription_id)
+        .bind(event)
+        .bind(payload)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(delivery)
+    }
+
+    // Claims up to `limit` due deliveries by pushing their next attempt `lease_secs` into the
+    // future. If the sender dies mid-request, the delivery simply becomes due again.
+    pub async fn claim_due(&self, limit: i64, lease_secs: f64) -> Result<Vec<DueDelivery>> {
+        let due = sqlx::query_as::<_, DueDelivery>(
+            r#"
+            WITH due AS (
+                SELECT d.id
+                FROM webhook_deliveries d
+                JOIN webhook_subscriptions s ON s.id = d.subscription_id AND s.active = true
+                WHERE d.status = 'pending' AND d.next_attempt_at <= NOW()
+                ORDER BY d.next_attempt_at
+                LIMIT $1
+                FOR UPDATE OF d SKIP LOCKED
+            )
+            UPDATE webhook_deliveries d
+            SET next_attempt_at = NOW() + make_interval(secs => $2)
+            FROM due, webhook_subscriptions s
+            WHERE d.id = due.id AND s.id = d.subscription_id
+            RETURNING d.id, d.event, d.payload, d.attempts, s.url, s.secret
+            "#,
+        )
+        .bind(limit)
+        .bind(lease_secs)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(due)
+    }
+
+    // Logs an attempt and moves the delivery to `status`. Pending deliveries are retried at
+    // `next_attempt_at`.
+    pub async fn record_attempt(
+        &self,
+        delivery_id: Uuid,
+        attempt: &DeliveryAttempt<'_>,
+        status: &str,
+        next_attempt_at: DateTime<Utc>,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await.map_err(AppError::DatabaseError)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_delivery_attempts (delivery_id, status_code, error, duration_ms)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(delivery_id)
+        .bind(attempt.status_code)
+        .bind(attempt.error)
+        .bind(attempt.duration_ms)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2,
+                attempts = attempts + 1,
+                next_attempt_at = $3,
+                last_status_code = $4,
+                last_error = $5,
+                delivered_at = CASE WHEN $2 = 'succeeded' THEN NOW() ELSE delivered_at END,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(delivery_id)
+        .bind(status)
+        .bind(next_attempt_at)
+        .bind(attempt.status_code)
+        .bind(attempt.error)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        tx.commit().await.map_err(AppError::DatabaseError)?;
+
+        Ok(())
+    }
+
+    pub async fn list_deliveries(&self, subscription_id: Uuid, limit: i64, offset: i64) -> Result<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            r#"
+            SELECT {}
+            FROM webhook_deliveries
+            WHERE subscription_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+            DELIVERY_COLUMNS
+        ))
+        .bind(subscription_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(deliveries)
+    }
+
+    pub async fn find_delivery(&self, subscription_id: Uuid, id: Uuid) -> Result<Option<WebhookDelivery>> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "SELECT {} FROM webhook_deliveries WHERE id = $1 AND subscription_id = $2",
+            DELIVERY_COLUMNS
+        ))
+        .bind(id)
+        .bind(subscription_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(delivery)
+    }
+
+    pub async fn find_attempts(&self, delivery_id: Uuid) -> Result<Vec<WebhookDeliveryAttempt>> {
+        let attempts = sqlx::query_as::<_, WebhookDeliveryAttempt>(
+            r#"
+            SELECT id, delivery_id, status_code, error, duration_ms, attempted_at
+            FROM webhook_delivery_attempts
+            WHERE delivery_id = $1
+            ORDER BY attempted_at
+            "#,
+        )
+        .bind(delivery_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(attempts)
+    }
+
+    // Puts a delivery back in the queue with a fresh retry budget; earlier attempts stay logged
+    pub async fn redeliver(&self, subscription_id: Uuid, id: Uuid) -> Result<Option<WebhookDelivery>> {
+        let delivery = sqlx::query_as::<_, WebhookDelivery>(&format!(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $3, attempts = 0, next_attempt_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND subscription_id = $2
+            RETURNING {}
+            "#,
+            DELIVERY_COLUMNS
+        ))
+        .bind(id)
+        .bind(subscription_id)
+        .bind(STATUS_PENDING)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::DatabaseError)?;
+
+        Ok(delivery)
+    }
+}
\ No newline at end of file
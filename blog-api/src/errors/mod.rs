@@ -1,6 +1,13 @@
-use axum::{response::IntoResponse, Json};
-use serde_json::json;
+use axum::{
+    http::{header, HeaderValue, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use serde::Serialize;
 use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::middleware::request_id;
 
 #[derive(Error, Debug)]
 pub enum AppError {
@@ -18,32 +25,74 @@ pub enum AppError {
 
     #[error("Bad request: {0}")]
     BadRequest(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Payload too large: {0}")]
+    PayloadTooLarge(String),
+
+    #[error("Unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+
+    #[error("Too many requests: retry after {0} seconds")]
+    TooManyRequests(u64),
+}
+
+// The JSON body of every error response
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: ErrorDetail,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorDetail {
+    pub message: String,
+    // The HTTP status code, repeated for clients that only see the body
+    pub code: u16,
+    // Matches the X-Request-Id response header and the request's log lines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl AppError {
+    // The status code for the error and a message that is safe to show to clients
+    pub fn status_and_message(&self) -> (StatusCode, String) {
+        match self {
+            AppError::ValidationError(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            AppError::NotFoundError(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            AppError::DatabaseError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()),
+            AppError::InternalError(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string()),
+            AppError::BadRequest(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            AppError::Unauthorized(_) => (StatusCode::UNAUTHORIZED, self.to_string()),
+            AppError::Forbidden(_) => (StatusCode::FORBIDDEN, self.to_string()),
+            AppError::PayloadTooLarge(_) => (StatusCode::PAYLOAD_TOO_LARGE, self.to_string()),
+            AppError::UnsupportedMediaType(_) => (StatusCode::UNSUPPORTED_MEDIA_TYPE, self.to_string()),
+            AppError::TooManyRequests(_) => (StatusCode::TOO_MANY_REQUESTS, self.to_string()),
+        }
+    }
 }
 
 impl IntoResponse for AppError {
     fn into_response(self) -> axum::response::Response {
-        let (status, err_msg) = match &self {
-            AppError::ValidationError(_) => (axum::http::StatusCode::BAD_REQUEST, self.to_string()),
-            AppError::NotFoundError(_) => (axum::http::StatusCode::NOT_FOUND, self.to_string()),
-            AppError::DatabaseError(_) => (
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            ),
-            AppError::InternalError(_) => (
-                axum::http::StatusCode::INTERNAL_SERVER_ERROR,
-                "Internal server error".to_string(),
-            ),
-            AppError::BadRequest(_) => (axum::http::StatusCode::BAD_REQUEST, self.to_string()),
-        };
-
-        let body = Json(json!({
-            "error": {
-                "message": err_msg,
-                "code": status.as_u16()
-            }
-        }));
-
-        (status, body).into_response()
+        let (status, err_msg) = self.status_and_message();
+
+        let body = Json(ErrorResponse {
+            error: ErrorDetail {
+                message: err_msg,
+                code: status.as_u16(),
+                request_id: request_id::current(),
+            },
+        });
+
+        let mut res = (status, body).into_response();
+        if let AppError::TooManyRequests(retry_after) = self {
+            res.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+        }
+        res
     }
 }
 
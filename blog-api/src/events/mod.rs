@@ -0,0 +1,181 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::Deserialize;
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::config::EventsConfig;
+use crate::db::EventRepository;
+use crate::models::event::PostEventData;
+use crate::models::post::Post;
+use crate::shutdown::Shutdown;
+
+// Postgres channel the live events are sent on
+const CHANNEL: &str = "blog_events";
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+// An event as relayed to SSE clients, with its data already serialized
+#[derive(Debug)]
+pub struct LiveEvent {
+    pub id: i64,
+    pub event: String,
+    pub author_id: Uuid,
+    pub published: bool,
+    pub data: String,
+}
+
+#[derive(Deserialize)]
+struct Notification {
+    id: i64,
+    event: String,
+    data: PostEventData,
+}
+
+// Live post events for SSE clients. Events are sent through Postgres NOTIFY rather than
+// straight to the local subscribers, so clients connected to any instance see every change.
+#[derive(Clone)]
+pub struct Events {
+    pool: PgPool,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    // Both are only touched with the lock held, so a new subscriber's replay and its live
+    // events never overlap or leave a gap
+    state: Mutex<State>,
+    capacity: usize,
+}
+
+struct State {
+    replay: VecDeque<Arc<LiveEvent>>,
+    sender: broadcast::Sender<Arc<LiveEvent>>,
+}
+
+impl Events {
+    // Live events stop, and SSE streams end, as soon as the listener closes on shutdown so
+    // that clients reconnect to another instance instead of holding up the drain
+    pub fn start(pool: PgPool, config: EventsConfig, shutdown: &Shutdown) -> Self {
+        let capacity = config.replay_buffer.max(1);
+        let (sender, _) = broadcast::channel(capacity);
+        let events = Self {
+            pool,
+            shared: Arc::new(Shared {
+                state: Mutex::new(State {
+                    replay: VecDeque::with_capacity(capacity),
+                    sender,
+                }),
+                capacity,
+            }),
+        };
+
+        shutdown.spawn(listen(events.pool.clone(), events.shared.clone(), shutdown.closing()));
+
+        events
+    }
+
+    // Failures are logged rather than returned, since the change has already been made
+    pub async fn publish(&self, event: &str, post: &Post) {
+        let data = PostEventData::from(post);
+        if let Err(e) = EventRepository::new(self.pool.clone()).notify(CHANNEL, event, &data).await {
+            tracing::error!("Failed to send {} live event: {}", event, e);
+        }
+    }
+
+    // Returns the buffered events after `last_event_id` along with a receiver for the ones
+    // that follow. Without an id, or when it has already left the buffer, the replay starts
+    // at the oldest event still buffered that is newer than it.
+    pub fn subscribe(&self, last_event_id: Option<i64>) -> (Vec<Arc<LiveEvent>>, broadcast::Receiver<Arc<LiveEvent>>) {
+        let state = self.shared.state.lock().expect("live event state poisoned");
+
+        let replay = match last_event_id {
+            None => Vec::new(),
+            Some(last) => match state.replay.iter().position(|e| e.id == last) {
+                // Concurrent NOTIFYs can commit out of id order, so resuming by position
+                // relays exactly what the client missed
+                Some(position) => state.replay.iter().skip(position + 1).cloned().collect(),
+                None => state.replay.iter().filter(|e| e.id > last).cloned().collect(),
+            },
+        };
+
+        (replay, state.sender.subscribe())
+    }
+}
+
+impl Shared {
+    fn relay(&self, event: LiveEvent) {
+        let event = Arc::new(event);
+        let mut state = self.state.lock().expect("live event state poisoned");
+
+        if state.replay.len() == self.capacity {
+            state.replay.pop_front();
+        }
+        state.replay.push_back(event.clone());
+        // Fails only when nobody is connected
+        let _ = state.sender.send(event);
+    }
+
+    // Dropping the sender ends each subscriber's stream once it has caught up
+    fn close(&self) {
+        let mut state = self.state.lock().expect("live event state poisoned");
+        state.sender = broadcast::channel(1).0;
+    }
+}
+
+async fn listen(pool: PgPool, shared: Arc<Shared>, token: CancellationToken) {
+    tokio::select! {
+        _ = receive(&pool, &shared) => {}
+        _ = token.cancelled() => {}
+    }
+    shared.close();
+}
+
+async fn receive(pool: &PgPool, shared: &Shared) {
+    let mut listener = loop {
+        match connect(pool).await {
+            Ok(listener) => break listener,
+            Err(e) => {
+                tracing::error!("Failed to listen for live events: {}", e);
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    };
+
+    loop {
+        match listener.try_recv().await {
+            Ok(Some(notification)) => match parse(notification.payload()) {
+                Ok(event) => shared.relay(event),
+                Err(e) => tracing::error!("Ignoring malformed live event: {}", e),
+            },
+            // The listener reconnects and listens again on the next call. Events sent in
+            // the meantime are lost for clients of this instance.
+            Ok(None) => tracing::warn!("Lost the live event connection, reconnecting"),
+            Err(e) => {
+                tracing::error!("Failed to receive live events: {}", e);
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+async fn connect(pool: &PgPool) -> Result<PgListener, sqlx::Error> {
+    let mut listener = PgListener::connect_with(pool).await?;
+    listener.listen(CHANNEL).await?;
+    Ok(listener)
+}
+
+fn parse(payload: &str) -> serde_json::Result<LiveEvent> {
+    let notification: Notification = serde_json::from_str(payload)?;
+
+    Ok(LiveEvent {
+        id: notification.id,
+        event: notification.event,
+        author_id: notification.data.author_id,
+        published: notification.data.published,
+        data: serde_json::to_string(&notification.data)?,
+    })
+}
\ No newline at end of file
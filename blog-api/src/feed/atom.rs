@@ -0,0 +1,38 @@
+use std::fmt::Write;
+
+use chrono::SecondsFormat;
+
+use super::{escape_xml, Feed};
+
+pub const CONTENT_TYPE: &str = "application/atom+xml; charset=utf-8";
+
+// Renders an Atom 1.0 (RFC 4287) document. Post content is plain text, so it is
+// emitted as escaped type="text" content rather than HTML.
+pub fn render(feed: &Feed) -> String {
+    let mut xml = String::new();
+
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#);
+    let _ = write!(xml, "<id>{}</id>", escape_xml(&feed.feed_url));
+    let _ = write!(xml, r#"<title type="text">{}</title>"#, escape_xml(&feed.title));
+    let _ = write!(xml, r#"<subtitle type="text">{}</subtitle>"#, escape_xml(&feed.description));
+    let _ = write!(xml, "<updated>{}</updated>", feed.updated.to_rfc3339_opts(SecondsFormat::Secs, true));
+    let _ = write!(xml, r#"<link rel="self" type="application/atom+xml" href="{}"/>"#, escape_xml(&feed.feed_url));
+    let _ = write!(xml, r#"<link rel="alternate" href="{}"/>"#, escape_xml(&feed.site_url));
+
+    for entry in &feed.entries {
+        xml.push_str("<entry>");
+        let _ = write!(xml, "<id>{}</id>", entry.guid());
+        let _ = write!(xml, r#"<title type="text">{}</title>"#, escape_xml(&entry.title));
+        let _ = write!(xml, r#"<link rel="alternate" href="{}"/>"#, escape_xml(&entry.url));
+        let _ = write!(xml, "<author><name>{}</name></author>", escape_xml(&entry.author_name));
+        let _ = write!(xml, "<published>{}</published>", entry.published.to_rfc3339_opts(SecondsFormat::Secs, true));
+        let _ = write!(xml, "<updated>{}</updated>", entry.updated.to_rfc3339_opts(SecondsFormat::Secs, true));
+        let _ = write!(xml, r#"<content type="text">{}</content>"#, escape_xml(&entry.content));
+        xml.push_str("</entry>");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
@@ -0,0 +1,58 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use super::Feed;
+
+pub const CONTENT_TYPE: &str = "application/feed+json; charset=utf-8";
+
+// JSON Feed 1.1, see https://www.jsonfeed.org/version/1.1/
+#[derive(Debug, Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    description: &'a str,
+    home_page_url: &'a str,
+    feed_url: &'a str,
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonFeedItem<'a> {
+    id: String,
+    url: &'a str,
+    title: &'a str,
+    content_text: &'a str,
+    date_published: DateTime<Utc>,
+    date_modified: DateTime<Utc>,
+    authors: Vec<JsonFeedAuthor<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonFeedAuthor<'a> {
+    name: &'a str,
+}
+
+pub fn render(feed: &Feed) -> serde_json::Result<String> {
+    let document = JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: &feed.title,
+        description: &feed.description,
+        home_page_url: &feed.site_url,
+        feed_url: &feed.feed_url,
+        items: feed
+            .entries
+            .iter()
+            .map(|entry| JsonFeedItem {
+                id: entry.guid(),
+                url: &entry.url,
+                title: &entry.title,
+                content_text: &entry.content,
+                date_published: entry.published,
+                date_modified: entry.updated,
+                authors: vec![JsonFeedAuthor { name: &entry.author_name }],
+            })
+            .collect(),
+    };
+
+    serde_json::to_string(&document)
+}
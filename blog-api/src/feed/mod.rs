@@ -0,0 +1,62 @@
+pub mod atom;
+pub mod json;
+pub mod rss;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+// Channel-level data shared by every feed format
+#[derive(Debug)]
+pub struct Feed {
+    pub title: String,
+    pub description: String,
+    pub site_url: String,
+    pub feed_url: String,
+    pub updated: DateTime<Utc>,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+pub struct Entry {
+    pub id: Uuid,
+    pub title: String,
+    pub url: String,
+    pub content: String,
+    pub author_name: String,
+    pub published: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl Entry {
+    // Post ids never change, so they make stable GUIDs independent of the public URL
+    pub fn guid(&self) -> String {
+        format!("urn:uuid:{}", self.id)
+    }
+}
+
+// Escapes text for use in XML element content and attribute values
+pub(crate) fn escape_xml(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than tab and newlines are not allowed in XML 1.0
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Wraps text in a CDATA section, splitting any "]]>" so the section cannot be closed early
+pub(crate) fn cdata(input: &str) -> String {
+    let cleaned: String = input
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    format!("<![CDATA[{}]]>", cleaned.replace("]]>", "]]]]><![CDATA[>"))
+}
@@ -0,0 +1,39 @@
+use std::fmt::Write;
+
+use super::{cdata, escape_xml, Feed};
+
+pub const CONTENT_TYPE: &str = "application/rss+xml; charset=utf-8";
+
+// Renders an RSS 2.0 document. Author names go in dc:creator because the RSS
+// <author> element requires an email address, which we never publish.
+pub fn render(feed: &Feed) -> String {
+    let mut xml = String::new();
+
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/">"#);
+    xml.push_str("<channel>");
+    let _ = write!(xml, "<title>{}</title>", escape_xml(&feed.title));
+    let _ = write!(xml, "<link>{}</link>", escape_xml(&feed.site_url));
+    let _ = write!(xml, "<description>{}</description>", escape_xml(&feed.description));
+    let _ = write!(
+        xml,
+        r#"<atom:link href="{}" rel="self" type="application/rss+xml"/>"#,
+        escape_xml(&feed.feed_url)
+    );
+    let _ = write!(xml, "<lastBuildDate>{}</lastBuildDate>", feed.updated.to_rfc2822());
+
+    for entry in &feed.entries {
+        xml.push_str("<item>");
+        let _ = write!(xml, "<title>{}</title>", escape_xml(&entry.title));
+        let _ = write!(xml, "<link>{}</link>", escape_xml(&entry.url));
+        let _ = write!(xml, r#"<guid isPermaLink="false">{}</guid>"#, entry.guid());
+        let _ = write!(xml, "<dc:creator>{}</dc:creator>", escape_xml(&entry.author_name));
+        let _ = write!(xml, "<pubDate>{}</pubDate>", entry.published.to_rfc2822());
+        let _ = write!(xml, "<description>{}</description>", cdata(&entry.content));
+        xml.push_str("</item>");
+    }
+
+    xml.push_str("</channel></rss>\n");
+    xml
+}
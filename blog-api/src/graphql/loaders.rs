@@ -0,0 +1,79 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use async_graphql::dataloader::Loader;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db::{FollowRepository, PostRepository, UserRepository};
+use crate::errors::AppError;
+use crate::models::follow::FollowCounts;
+use crate::models::post::Post;
+use crate::models::user::User;
+
+// Batches the `author` lookups of every post in a response into one query
+pub struct UserLoader(pub PgPool);
+
+impl Loader<Uuid> for UserLoader {
+    type Value = User;
+    type Error = Arc<AppError>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, User>, Self::Error> {
+        let users = UserRepository::new(self.0.clone()).find_by_ids(keys).await.map_err(Arc::new)?;
+
+        Ok(users.into_iter().map(|user| (user.id, user)).collect())
+    }
+}
+
+pub struct FollowCountsLoader(pub PgPool);
+
+impl Loader<Uuid> for FollowCountsLoader {
+    type Value = FollowCounts;
+    type Error = Arc<AppError>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, FollowCounts>, Self::Error> {
+        let counts = FollowRepository::new(self.0.clone()).counts(keys).await.map_err(Arc::new)?;
+
+        Ok(counts.into_iter().map(|c| (c.user_id, c)).collect())
+    }
+}
+
+// A user's `posts` field, keyed by its arguments as well since they change the query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AuthorPosts {
+    pub author_id: Uuid,
+    pub limit: i64,
+    pub published_only: bool,
+}
+
+pub struct AuthorPostsLoader(pub PgPool);
+
+impl Loader<AuthorPosts> for AuthorPostsLoader {
+    type Value = Vec<Post>;
+    type Error = Arc<AppError>;
+
+    async fn load(&self, keys: &[AuthorPosts]) -> Result<HashMap<AuthorPosts, Vec<Post>>, Self::Error> {
+        // One query per distinct set of arguments, which is almost always just one
+        let mut groups: BTreeMap<(i64, bool), Vec<Uuid>> = BTreeMap::new();
+        for key in keys {
+            groups.entry((key.limit, key.published_only)).or_default().push(key.author_id);
+        }
+
+        let repo = PostRepository::new(self.0.clone());
+        let mut loaded = HashMap::new();
+        for ((limit, published_only), author_ids) in groups {
+            let mut by_author: HashMap<Uuid, Vec<Post>> = HashMap::new();
+            for post in repo.find_by_authors(&author_ids, limit, published_only).await.map_err(Arc::new)? {
+                by_author.entry(post.author_id).or_default().push(post);
+            }
+
+            for author_id in author_ids {
+                let mut posts = by_author.remove(&author_id).unwrap_or_default();
+                posts.sort_by_key(|post| std::cmp::Reverse(post.created_at));
+                loaded.insert(AuthorPosts { author_id, limit, published_only }, posts);
+            }
+        }
+
+        Ok(loaded)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,180 @@
+mod loaders;
+mod types;
+
+use std::sync::Arc;
+
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Context, EmptySubscription, ErrorExtensions, Object, Result, ResultExt, Schema};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::posts::{self, PostHooks};
+use crate::api::users;
+use crate::config::GraphqlConfig;
+use crate::db::{PostRepository, UserRepository};
+use crate::errors::AppError;
+use crate::middleware::rate_limit::RateLimitLayer;
+use crate::middleware::{request_id, RateLimitClient, RateLimiter};
+use crate::storage::MediaStore;
+use crate::webhooks::Webhooks;
+use loaders::{AuthorPostsLoader, FollowCountsLoader, UserLoader};
+use types::{CreatePostInput, CreateUserInput, PostObject, UpdatePostInput, UpdateUserInput, UserObject};
+
+// Upper bound for `limit` arguments, which also caps how much a list adds to a query's complexity
+const MAX_LIMIT: i64 = 100;
+
+pub type BlogSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+// Everything the resolvers need besides the loaders
+struct Services {
+    pool: PgPool,
+    hooks: PostHooks,
+    webhooks: Webhooks,
+    media_store: Arc<dyn MediaStore>,
+    // Buckets of the REST routes that create users and posts
+    signup_limit: RateLimitLayer,
+    posts_limit: RateLimitLayer,
+}
+
+pub fn build_schema(
+    pool: PgPool,
+    config: &GraphqlConfig,
+    hooks: PostHooks,
+    webhooks: Webhooks,
+    media_store: Arc<dyn MediaStore>,
+    limiter: &RateLimiter,
+) -> BlogSchema {
+    let services = Services {
+        pool: pool.clone(),
+        hooks,
+        webhooks,
+        media_store,
+        signup_limit: limiter.ip_layer("signup"),
+        posts_limit: limiter.layer("posts"),
+    };
+
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .limit_depth(config.max_depth)
+        .limit_complexity(config.max_complexity)
+        .data(DataLoader::new(UserLoader(pool.clone()), tokio::spawn))
+        .data(DataLoader::new(FollowCountsLoader(pool.clone()), tokio::spawn))
+        .data(DataLoader::new(AuthorPostsLoader(pool), tokio::spawn))
+        .data(services)
+        .finish()
+}
+
+// Errors carry the same message and status code as the REST API would return
+impl ErrorExtensions for AppError {
+    fn extend(&self) -> async_graphql::Error {
+        let (status, message) = self.status_and_message();
+        if status.is_server_error() {
+            tracing::error!("GraphQL request failed: {}", self);
+        }
+
+        async_graphql::Error::new(message).extend_with(|_, e| {
+            e.set("code", status.as_u16());
+            if let Some(request_id) = request_id::current() {
+                e.set("requestId", request_id);
+            }
+        })
+    }
+}
+
+fn services<'a>(ctx: &Context<'a>) -> &'a Services {
+    ctx.data_unchecked::<Services>()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn post(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<PostObject>> {
+        let post = PostRepository::new(services(ctx).pool.clone()).find_by_id(id).await.extend()?;
+        Ok(post.map(PostObject))
+    }
+
+    #[graphql(complexity = "limit.clamp(0, MAX_LIMIT) as usize * child_complexity")]
+    async fn posts(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 10, validator(minimum = 1, maximum = 100))] limit: i64,
+        #[graphql(default = 0, validator(minimum = 0))] offset: i64,
+        #[graphql(default = false)] published_only: bool,
+    ) -> Result<Vec<PostObject>> {
+        let posts = PostRepository::new(services(ctx).pool.clone())
+            .list(limit, offset, published_only)
+            .await
+            .extend()?;
+        Ok(posts.into_iter().map(PostObject).collect())
+    }
+
+    async fn user(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<UserObject>> {
+        let user = UserRepository::new(services(ctx).pool.clone()).find_by_id(id).await.extend()?;
+        Ok(user.map(UserObject))
+    }
+
+    #[graphql(complexity = "limit.clamp(0, MAX_LIMIT) as usize * child_complexity")]
+    async fn users(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 10, validator(minimum = 1, maximum = 100))] limit: i64,
+        #[graphql(default = 0, validator(minimum = 0))] offset: i64,
+    ) -> Result<Vec<UserObject>> {
+        let users = UserRepository::new(services(ctx).pool.clone()).list(limit, offset).await.extend()?;
+        Ok(users.into_iter().map(UserObject).collect())
+    }
+}
+
+// Mutations go through the same code as the REST handlers, so validation, webhooks, live
+// events and notifications behave identically
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn create_user(&self, ctx: &Context<'_>, input: CreateUserInput) -> Result<UserObject> {
+        let services = services(ctx);
+        charge(ctx, &services.signup_limit).await?;
+        let user = users::create(&services.pool, &services.webhooks, &input.into()).await.extend()?;
+        Ok(UserObject(user))
+    }
+
+    async fn update_user(&self, ctx: &Context<'_>, id: Uuid, input: UpdateUserInput) -> Result<UserObject> {
+        let user = users::update(&services(ctx).pool, id, &input.into()).await.extend()?;
+        Ok(UserObject(user))
+    }
+
+    async fn delete_user(&self, ctx: &Context<'_>, id: Uuid) -> Result<bool> {
+        let services = services(ctx);
+        users::delete(&services.pool, services.media_store.as_ref(), id).await.extend()?;
+        Ok(true)
+    }
+
+    async fn create_post(&self, ctx: &Context<'_>, author_id: Uuid, input: CreatePostInput) -> Result<PostObject> {
+        let services = services(ctx);
+        charge(ctx, &services.posts_limit).await?;
+        let post = posts::create(&services.pool, &services.hooks, author_id, &input.into()).await.extend()?;
+        Ok(PostObject(post))
+    }
+
+    async fn update_post(&self, ctx: &Context<'_>, id: Uuid, input: UpdatePostInput) -> Result<PostObject> {
+        let services = services(ctx);
+        charge(ctx, &services.posts_limit).await?;
+        let post = posts::update(&services.pool, &services.hooks, id, &input.into()).await.extend()?;
+        Ok(PostObject(post))
+    }
+
+    async fn delete_post(&self, ctx: &Context<'_>, id: Uuid) -> Result<bool> {
+        let services = services(ctx);
+        posts::delete(&services.pool, &services.hooks, id).await.extend()?;
+        Ok(true)
+    }
+}
+
+// Each mutation field counts against the bucket of its REST route, so aliasing one
+// mutation many times in an operation doesn't get around the limit
+async fn charge(ctx: &Context<'_>, limit: &RateLimitLayer) -> Result<()> {
+    match ctx.data_opt::<RateLimitClient>() {
+        Some(client) => limit.check(client).await.extend(),
+        None => Ok(()),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,167 @@
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Context, ErrorExtensions, InputObject, Object, Result};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::loaders::{AuthorPosts, AuthorPostsLoader, FollowCountsLoader, UserLoader};
+use super::MAX_LIMIT;
+use crate::errors::AppError;
+use crate::models::post::{CreatePostRequest, Post, UpdatePostRequest};
+use crate::models::user::{CreateUserRequest, UpdateUserRequest, User};
+
+pub struct PostObject(pub Post);
+
+#[Object(name = "Post")]
+impl PostObject {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn content(&self) -> &str {
+        &self.0.content
+    }
+
+    async fn author_id(&self) -> Uuid {
+        self.0.author_id
+    }
+
+    async fn published(&self) -> bool {
+        self.0.published
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.0.updated_at
+    }
+
+    async fn author(&self, ctx: &Context<'_>) -> Result<UserObject> {
+        let author = ctx
+            .data_unchecked::<DataLoader<UserLoader>>()
+            .load_one(self.0.author_id)
+            .await
+            .map_err(|e| e.extend())?
+            .ok_or_else(|| AppError::NotFoundError(format!("User with id {} not found", self.0.author_id)).extend())?;
+
+        Ok(UserObject(author))
+    }
+}
+
+pub struct UserObject(pub User);
+
+#[Object(name = "User")]
+impl UserObject {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn username(&self) -> &str {
+        &self.0.username
+    }
+
+    async fn email(&self) -> &str {
+        &self.0.email
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    async fn follower_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        let counts = ctx.data_unchecked::<DataLoader<FollowCountsLoader>>().load_one(self.0.id).await.map_err(|e| e.extend())?;
+        Ok(counts.map_or(0, |c| c.follower_count))
+    }
+
+    async fn following_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        let counts = ctx.data_unchecked::<DataLoader<FollowCountsLoader>>().load_one(self.0.id).await.map_err(|e| e.extend())?;
+        Ok(counts.map_or(0, |c| c.following_count))
+    }
+
+    // The user's latest posts
+    #[graphql(complexity = "limit.clamp(0, MAX_LIMIT) as usize * child_complexity")]
+    async fn posts(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 10, validator(minimum = 1, maximum = 100))] limit: i64,
+        #[graphql(default = false)] published_only: bool,
+    ) -> Result<Vec<PostObject>> {
+        let key = AuthorPosts { author_id: self.0.id, limit, published_only };
+        let posts = ctx.data_unchecked::<DataLoader<AuthorPostsLoader>>().load_one(key).await.map_err(|e| e.extend())?;
+
+        Ok(posts.unwrap_or_default().into_iter().map(PostObject).collect())
+    }
+}
+
+#[derive(InputObject)]
+pub struct CreateUserInput {
+    pub username: String,
+    pub email: String,
+    pub password: String,
+}
+
+impl From<CreateUserInput> for CreateUserRequest {
+    fn from(input: CreateUserInput) -> Self {
+        Self {
+            username: input.username,
+            email: input.email,
+            password: input.password,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct UpdateUserInput {
+    pub username: Option<String>,
+    pub email: Option<String>,
+    pub password: Option<String>,
+}
+
+impl From<UpdateUserInput> for UpdateUserRequest {
+    fn from(input: UpdateUserInput) -> Self {
+        Self {
+            username: input.username,
+            email: input.email,
+            password: input.password,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct CreatePostInput {
+    pub title: String,
+    pub content: String,
+    pub published: Option<bool>,
+}
+
+impl From<CreatePostInput> for CreatePostRequest {
+    fn from(input: CreatePostInput) -> Self {
+        Self {
+            title: input.title,
+            content: input.content,
+            published: input.published,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct UpdatePostInput {
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub published: Option<bool>,
+}
+
+impl From<UpdatePostInput> for UpdatePostRequest {
+    fn from(input: UpdatePostInput) -> Self {
+        Self {
+            title: input.title,
+            content: input.content,
+            published: input.published,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,303 @@
+use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::body::Bytes;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use image::{DynamicImage, ExtendedColorType, ImageDecoder, ImageReader, Limits};
+use sqlx::PgPool;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+use crate::config::ImagesConfig;
+use crate::db::ImageRepository;
+use crate::models::image::{file_extension, ImageVariant};
+use crate::shutdown::Shutdown;
+use crate::storage::MediaStore;
+
+// Uploaded originals are kept only until their variants have been generated
+pub fn original_key(id: Uuid) -> String {
+    format!("{}.original", id)
+}
+
+fn variant_key(id: Uuid, name: &str, format: &str) -> String {
+    format!("{}-{}.{}", id, name, file_extension(format))
+}
+
+// Removes the original and every variant file of an image, logging failures
+pub async fn delete_files(store: &dyn MediaStore, id: Uuid, variants: &[ImageVariant]) {
+    let keys = variants
+        .iter()
+        .map(|v| v.storage_key.clone())
+        .chain(std::iter::once(original_key(id)));
+
+    for key in keys {
+        if let Err(e) = store.delete(&key).await {
+            tracing::error!("Failed to remove image file {}: {}", key, e);
+        }
+    }
+}
+
+// How often images left pending because the queue was full are queued again
+const SWEEP_INTERVAL: Duration = Duration::from_secs(10);
+
+// Queue of uploaded images waiting to be resized. Work is identified by image id
+// only, so anything left pending in the database is picked up again by a sweep: once
+// on startup, and whenever an upload found the queue full.
+#[derive(Clone)]
+pub struct ImagePipeline {
+    sender: mpsc::Sender<Uuid>,
+    // Set when an image could not be queued, so the next sweep looks for pending images
+    backlog: Arc<AtomicBool>,
+}
+
+#[derive(Clone)]
+struct Worker {
+    pool: PgPool,
+    store: Arc<dyn MediaStore>,
+    config: Arc<ImagesConfig>,
+}
+
+impl ImagePipeline {
+    // Workers stop taking new images once shutdown cancels them; whatever is still queued
+    // stays pending in the database for the next start.
+    pub fn start(pool: PgPool, store: Arc<dyn MediaStore>, config: ImagesConfig, shutdown: &Shutdown) -> Self {
+        let (sender, receiver) = mpsc::channel(config.queue_size.max(1));
+        let receiver = Arc::new(Mutex::new(receiver));
+        let worker = Worker {
+            pool: pool.clone(),
+            store,
+            config: Arc::new(config),
+        };
+
+        for _ in 0..worker.config.workers.max(1) {
+            let worker = worker.clone();
+            let receiver = receiver.clone();
+            let token = shutdown.token();
+            shutdown.spawn(async move {
+                loop {
+                    let next = tokio::select! {
+                        _ = token.cancelled() => break,
+                        next = async { receiver.lock().await.recv().await } => next,
+                    };
+                    match next {
+                        Some(id) => worker.process(id).await,
+                        None => break,
+                    }
+                }
+            });
+        }
+
+        let pipeline = Self {
+            sender,
+            backlog: Arc::new(AtomicBool::new(true)),
+        };
+        let sweeper = pipeline.clone();
+        let token = shutdown.token();
+        shutdown.spawn(async move {
+            let repo = ImageRepository::new(pool);
+            if let Err(e) = repo.reset_processing().await {
+                tracing::error!("Failed to requeue interrupted images: {}", e);
+            }
+
+            let mut interval = tokio::time::interval(SWEEP_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+                if !sweeper.backlog.swap(false, Ordering::Relaxed) {
+                    continue;
+                }
+
+                let ids = match repo.find_pending().await {
+                    Ok(ids) => ids,
+                    Err(e) => {
+                        tracing::error!("Failed to load pending images: {}", e);
+                        sweeper.backlog.store(true, Ordering::Relaxed);
+                        continue;
+                    }
+                };
+                // Unlike uploads, the sweep waits for room in the queue
+                for id in ids {
+                    tokio::select! {
+                        _ = token.cancelled() => return,
+                        sent = sweeper.sender.send(id) => {
+                            if sent.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        pipeline
+    }
+
+    // Never waits: when the queue is full the image stays pending until the next sweep
+    pub fn enqueue(&self, id: Uuid) {
+        match self.sender.try_send(id) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                self.backlog.store(true, Ordering::Relaxed);
+                tracing::warn!("Image queue is full, image {} stays pending until the next sweep", id);
+            }
+            Err(TrySendError::Closed(_)) => {
+                tracing::error!("Image pipeline is not running, image {} stays pending", id);
+            }
+        }
+    }
+}
+
+struct Rendered {
+    name: String,
+    format: &'static str,
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl Worker {
+    async fn process(&self, id: Uuid) {
+        let repo = ImageRepository::new(self.pool.clone());
+        match repo.claim(id).await {
+            Ok(true) => {}
+            // Queued twice and already handled
+            Ok(false) => return,
+            Err(e) => {
+                tracing::error!("Failed to start processing image {}: {}", id, e);
+                return;
+            }
+        }
+
+        match self.generate_variants(id).await {
+            Ok((width, height, variants)) => {
+                if let Err(e) = repo.mark_ready(id, width as i32, height as i32, &variants).await {
+                    tracing::error!("Failed to record variants of image {}: {}", id, e);
+                    delete_files(self.store.as_ref(), id, &variants).await;
+                    let _ = repo.mark_failed(id, "Failed to record variants").await;
+                    return;
+                }
+                tracing::info!("Processed image {} into {} variants", id, variants.len());
+            }
+            Err(message) => {
+                tracing::warn!("Failed to process image {}: {}", id, message);
+                if let Err(e) = repo.mark_failed(id, &message).await {
+                    tracing::error!("Failed to mark image {} as failed: {}", id, e);
+                }
+            }
+        }
+
+        // The original may carry EXIF and GPS metadata, so it is never kept
+        if let Err(e) = self.store.delete(&original_key(id)).await {
+            tracing::error!("Failed to remove original of image {}: {}", id, e);
+        }
+    }
+
+    async fn generate_variants(&self, id: Uuid) -> Result<(u32, u32, Vec<ImageVariant>), String> {
+        let original = self
+            .store
+            .get(&original_key(id), 0..u64::MAX)
+            .await
+            .map_err(|e| format!("Original upload is missing: {}", e))?;
+        let original = axum::body::to_bytes(original, usize::MAX)
+            .await
+            .map_err(|e| format!("Failed to read original upload: {}", e))?;
+
+        let config = self.config.clone();
+        let (width, height, rendered) = tokio::task::spawn_blocking(move || render(&original, &config))
+            .await
+            .map_err(|e| format!("Image worker panicked: {}", e))??;
+
+        let mut variants = Vec::with_capacity(rendered.len());
+        for r in rendered {
+            let storage_key = variant_key(id, &r.name, r.format);
+            let size_bytes = r.data.len() as i64;
+            if let Err(e) = self.store.put(&storage_key, Bytes::from(r.data)).await {
+                delete_files(self.store.as_ref(), id, &variants).await;
+                return Err(format!("Failed to store variant: {}", e));
+            }
+            variants.push(ImageVariant {
+                image_id: id,
+                name: r.name,
+                format: r.format.to_string(),
+                width: r.width as i32,
+                height: r.height as i32,
+                size_bytes,
+                storage_key,
+            });
+        }
+
+        Ok((width, height, variants))
+    }
+}
+
+// Decodes the upload and produces every variant. Re-encoding from raw pixels is
+// what strips EXIF, GPS and any other metadata from the output files.
+fn render(data: &[u8], config: &ImagesConfig) -> Result<(u32, u32, Vec<Rendered>), String> {
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(config.max_dimension);
+    limits.max_image_height = Some(config.max_dimension);
+
+    let mut reader = ImageReader::new(Cursor::new(data))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+    reader.limits(limits);
+
+    let mut decoder = reader.into_decoder().map_err(|e| format!("Unsupported image: {}", e))?;
+    // Apply the EXIF orientation before the metadata is thrown away
+    let orientation = decoder.orientation().map_err(|e| e.to_string())?;
+    let mut image = DynamicImage::from_decoder(decoder).map_err(|e| format!("Invalid image: {}", e))?;
+    image.apply_orientation(orientation);
+
+    let (width, height) = (image.width(), image.height());
+    let mut targets: Vec<(String, DynamicImage)> = Vec::new();
+
+    let size = config.thumbnail_size;
+    targets.push(("thumb".to_string(), image.resize_to_fill(size, size, FilterType::Lanczos3)));
+
+    // Never upscale: widths larger than the original collapse into one full-size variant
+    let mut widths: Vec<u32> = config.widths.iter().map(|w| (*w).min(width)).collect();
+    widths.sort_unstable();
+    widths.dedup();
+    for w in widths {
+        let resized = if w == width {
+            image.clone()
+        } else {
+            image.resize(w, u32::MAX, FilterType::Lanczos3)
+        };
+        targets.push((format!("w{}", w), resized));
+    }
+
+    let mut rendered = Vec::with_capacity(targets.len() * 2);
+    for (name, img) in targets {
+        let mut jpeg = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg, config.jpeg_quality)
+            .encode_image(&img.to_rgb8())
+            .map_err(|e| format!("Failed to encode JPEG: {}", e))?;
+
+        // The image crate's pure-Rust WebP encoder only writes lossless files
+        let rgba = img.to_rgba8();
+        let mut webp = Vec::new();
+        WebPEncoder::new_lossless(&mut webp)
+            .encode(&rgba, rgba.width(), rgba.height(), ExtendedColorType::Rgba8)
+            .map_err(|e| format!("Failed to encode WebP: {}", e))?;
+
+        for (format, data) in [("jpeg", jpeg), ("webp", webp)] {
+            rendered.push(Rendered {
+                name: name.clone(),
+                format,
+                width: img.width(),
+                height: img.height(),
+                data,
+            });
+        }
+    }
+
+    Ok((width, height, rendered))
+}
@@ -0,0 +1,38 @@
+pub mod redact;
+
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+use crate::config::{LogFormat, LoggingConfig};
+use redact::Redacting;
+
+// Installs the global subscriber. The level comes from RUST_LOG and defaults to info, and
+// also limits which spans are exported when a tracer provider is given.
+pub fn init(config: &LoggingConfig, tracer_provider: Option<&SdkTracerProvider>) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into());
+    let writer = Redacting(std::io::stdout);
+
+    let (text, json) = match config.format {
+        LogFormat::Text => (Some(tracing_subscriber::fmt::layer().with_writer(writer)), None),
+        LogFormat::Json => (
+            None,
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_current_span(true)
+                    .with_span_list(false)
+                    .with_writer(writer),
+            ),
+        ),
+    };
+
+    let otel = tracer_provider.map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer("blog-api")));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(text)
+        .with(json)
+        .with(otel)
+        .init();
+}
\ No newline at end of file
@@ -2,38 +2,63 @@ mod api;
 mod config;
 mod db;
 mod errors;
+mod events;
+mod feed;
+mod graphql;
+mod images;
+mod logging;
+mod middleware;
 mod models;
+mod notifications;
+mod og_image;
+mod shutdown;
+mod storage;
+mod telemetry;
+mod tls;
+mod webhooks;
 
+use std::future::IntoFuture;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
 
-use axum::http::{HeaderValue, Method};
+use futures_util::future::{BoxFuture, FutureExt};
 use config::AppConfig;
-use tower_http::cors::{Any, CorsLayer};
-use tower_http::trace::TraceLayer;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use events::Events;
+use images::ImagePipeline;
+use middleware::{RequestIdLayer, SecurityHeadersLayer};
+use notifications::Notifier;
+use shutdown::Shutdown;
+use storage::LocalMediaStore;
+use telemetry::metrics::HttpMetricsLayer;
+use webhooks::Webhooks;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    // Load configuration before tracing is set up, since it selects the log format
+    let (config, config_error) = match AppConfig::from_env() {
+        Ok(config) => (config, None),
+        Err(e) => (AppConfig::default_development(), Some(e)),
+    };
+
     // Initialize tracing
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
-        .with(tracing_subscriber::fmt::layer())
-        .init();
+    let tracer_provider = telemetry::otel::init(&config.opentelemetry)?;
+    logging::init(&config.logging, tracer_provider.as_ref());
 
     tracing::info!("Starting blog API server");
-
-    // Load configuration
-    let config = AppConfig::from_env().unwrap_or_else(|_| {
-        tracing::warn!("Failed to load config from environment, using default development config");
-        AppConfig::default_development()
-    });
+    if tracer_provider.is_some() {
+        tracing::info!("Exporting traces to {}", config.opentelemetry.endpoint);
+    }
+    if let Some(e) = config_error {
+        tracing::warn!("Failed to load config from environment, using default development config: {}", e);
+    }
 
     tracing::info!(
         "Loaded configuration: server at {}:{}, database URL: {}",
         config.server.host, 
         config.server.port,
         // Masked URL to avoid leaking credentials in logs
-        config.database.url.split('@').last().unwrap_or("<masked>")
+        config.database.url.split('@').next_back().unwrap_or("<masked>")
     );
 
     // Set up database connection
@@ -49,26 +74,135 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    // Background tasks are spawned through this so they can be stopped on shutdown
+    let shutdown = Shutdown::default();
+
+    // Set up metrics before anything records them
+    let metrics = if config.metrics.enabled {
+        Some(telemetry::metrics::install(&shutdown)?)
+    } else {
+        None
+    };
+
+    // Set up media storage
+    let media_store = Arc::new(LocalMediaStore::new(&config.media.directory).await?);
+    let image_pipeline = ImagePipeline::start(pool.clone(), media_store.clone(), config.images.clone(), &shutdown);
+    let notifier = Notifier::start(pool.clone(), &shutdown);
+    let webhooks = Webhooks::start(pool.clone(), config.webhooks.clone(), &shutdown);
+    let events = Events::start(pool.clone(), config.events.clone(), &shutdown);
+
     // Set up CORS
-    let cors = CorsLayer::new()
-        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers(Any)
-        .allow_origin(Any); // Allow any origin for browser access
+    let cors = middleware::cors::cors_layer(&config.cors)?;
 
     // Build our application with routes
-    let app = api::create_router(pool.clone())
-        .layer(TraceLayer::new_for_http())
-        .layer(cors);
+    let mut app = api::create_router(pool.clone(), &config, media_store, image_pipeline, notifier, webhooks, events);
+
+    // Readiness reports "not ready" once shutdown begins
+    app = app.merge(api::health::create_router(pool.clone(), &config, shutdown.clone()));
+
+    if let Some(handle) = metrics {
+        let metrics_router = telemetry::metrics::create_router(handle, pool.clone());
+        match &config.metrics.address {
+            Some(address) => {
+                let metrics_addr: SocketAddr = address.parse()?;
+                let listener = tokio::net::TcpListener::bind(metrics_addr).await?;
+                tracing::info!("Serving metrics on {}", metrics_addr);
+                // Kept up until background tasks stop, so the drain itself can be scraped
+                let token = shutdown.token();
+                shutdown.spawn(async move {
+                    let server = axum::serve(listener, metrics_router).with_graceful_shutdown(token.cancelled_owned());
+                    if let Err(e) = server.await {
+                        tracing::error!("Metrics server failed: {}", e);
+                    }
+                });
+            }
+            None => app = app.merge(metrics_router),
+        }
+    }
+
+    let app = app
+        .layer(HttpMetricsLayer)
+        .layer(telemetry::http::trace_layer())
+        .layer(RequestIdLayer)
+        .layer(cors)
+        .layer(SecurityHeadersLayer::new(&config.security_headers));
+
+    // Load the certificate up front so a bad one stops startup
+    let tls = if config.server.tls.enabled {
+        Some(tls::server_config(&config.server.tls, &shutdown).await?)
+    } else {
+        None
+    };
+    let scheme = if tls.is_some() { "https" } else { "http" };
 
     // Run the server
     let addr = format!("{}{}{}", config.server.host, ":", config.server.port);
     let socket_addr: SocketAddr = addr.parse()?;
     
-    tracing::info!("Listening on {}", socket_addr);
-    tracing::info!("Web UI available at: http://{}:{}/ui", config.server.host, config.server.port);
+    tracing::info!("Listening on {} ({})", socket_addr, scheme);
+    tracing::info!("Web UI available at: {}://{}:{}/ui", scheme, config.server.host, config.server.port);
     
     let listener = tokio::net::TcpListener::bind(socket_addr).await?;
-    axum::serve(listener, app.into_make_service()).await?;
+    if let Some(port) = config.server.tls.redirect_port.filter(|_| tls.is_some()) {
+        let redirect_addr: SocketAddr = format!("{}:{}", config.server.host, port).parse()?;
+        let listener = tokio::net::TcpListener::bind(redirect_addr).await?;
+        tracing::info!("Redirecting HTTP on {} to HTTPS", redirect_addr);
+        let https_port = config.server.port;
+        let closing = shutdown.closing();
+        shutdown.spawn(async move {
+            let server = axum::serve(listener, tls::redirect_router(https_port))
+                .with_graceful_shutdown(closing.cancelled_owned());
+            if let Err(e) = server.await {
+                tracing::error!("HTTP redirect server failed: {}", e);
+            }
+        });
+    }
+
+    let signal = shutdown.clone().triggered(Duration::from_secs(config.shutdown.delay_secs));
+    // Connection info lets the rate limiter key anonymous clients by address
+    let mut server: BoxFuture<'static, std::io::Result<()>> = match tls {
+        Some(tls) => tls::serve(listener, app, tls, signal).boxed(),
+        None => axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+            .with_graceful_shutdown(signal)
+            .into_future()
+            .boxed(),
+    };
+
+
+    let closing = shutdown.closing();
+    let finished = tokio::select! {
+        result = &mut server => {
+            result?;
+            true
+        }
+        _ = closing.cancelled() => false,
+    };
+
+    // In-flight requests, background tasks and closing the database pool share the deadline
+    let timeout = Duration::from_secs(config.shutdown.timeout_secs);
+    let deadline = tokio::time::Instant::now() + timeout;
+    if !finished {
+        match tokio::time::timeout_at(deadline, server).await {
+            Ok(result) => result?,
+            Err(_) => tracing::warn!("In-flight requests did not finish within {}s, dropping them", timeout.as_secs()),
+        }
+    }
+
+    tracing::info!("Stopping background tasks");
+    if !shutdown.stop_tasks(deadline).await {
+        tracing::warn!("Background tasks did not finish within {}s, dropping them", timeout.as_secs());
+    }
+
+    if tokio::time::timeout_at(deadline, pool.close()).await.is_err() {
+        tracing::warn!("Database connections did not close within {}s, dropping them", timeout.as_secs());
+    }
+
+    // Export the spans still queued
+    if let Some(provider) = tracer_provider {
+        if let Err(e) = provider.shutdown() {
+            tracing::warn!("Failed to flush traces: {}", e);
+        }
+    }
 
     Ok(())
 }
\ No newline at end of file
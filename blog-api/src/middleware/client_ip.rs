@@ -0,0 +1,64 @@
+use std::net::{IpAddr, SocketAddr};
+
+use axum::extract::ConnectInfo;
+use axum::http::{HeaderMap, Request};
+use ipnet::IpNet;
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// Reverse proxies allowed to report the client address through `X-Forwarded-For`.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies(Vec<IpNet>);
+
+impl TrustedProxies {
+    // Accepts single addresses as well as CIDR ranges; invalid entries are skipped
+    pub fn new(entries: &[String]) -> Self {
+        let nets = entries
+            .iter()
+            .filter_map(|entry| {
+                let parsed = entry
+                    .parse::<IpNet>()
+                    .or_else(|_| entry.parse::<IpAddr>().map(IpNet::from));
+                if parsed.is_err() {
+                    tracing::warn!("Ignoring invalid trusted proxy {:?}", entry);
+                }
+                parsed.ok()
+            })
+            .collect();
+        Self(nets)
+    }
+
+    fn contains(&self, ip: &IpAddr) -> bool {
+        self.0.iter().any(|net| net.contains(ip))
+    }
+
+    /// The address of the client that sent the request. Requires the server to be
+    /// started with `into_make_service_with_connect_info::<SocketAddr>()`.
+    pub fn client_ip<B>(&self, req: &Request<B>) -> Option<IpAddr> {
+        let peer = req.extensions().get::<ConnectInfo<SocketAddr>>()?.0.ip();
+        Some(self.resolve(peer, req.headers()))
+    }
+
+    // Walks X-Forwarded-For from the nearest hop and stops at the first address that
+    // is not a trusted proxy, so clients cannot spoof their address by prepending entries
+    fn resolve(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let hops = headers
+            .get_all(X_FORWARDED_FOR)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .collect::<Vec<_>>();
+
+        let mut client = peer;
+        for hop in hops.into_iter().rev() {
+            if !self.contains(&client) {
+                break;
+            }
+            match hop.trim().parse::<IpAddr>() {
+                Ok(ip) => client = ip,
+                Err(_) => break,
+            }
+        }
+        client
+    }
+}
\ No newline at end of file
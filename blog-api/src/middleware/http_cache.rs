@@ -0,0 +1,256 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::SystemTime;
+
+use axum::body::{Body, Bytes};
+use axum::http::{header, HeaderMap, HeaderValue, Method, Request, Response, StatusCode};
+use axum::response::{IntoResponseParts, ResponseParts};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use tower::{BoxError, Layer, Service};
+
+// Bodies larger than this are passed through without an ETag rather than buffered
+const MAX_ETAG_BODY_SIZE: u64 = 1024 * 1024;
+
+/// Marks a response as private so the layer applies the private Cache-Control policy.
+/// Handlers return this for drafts or any listing that may include them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Public,
+    Private,
+}
+
+impl IntoResponseParts for Visibility {
+    type Error = std::convert::Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        res.extensions_mut().insert(self);
+        Ok(res)
+    }
+}
+
+/// Sets the `Last-Modified` header from a row's `updated_at`.
+#[derive(Debug, Clone, Copy)]
+pub struct LastModified(pub DateTime<Utc>);
+
+impl IntoResponseParts for LastModified {
+    type Error = std::convert::Infallible;
+
+    fn into_response_parts(self, mut res: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        let value = httpdate::fmt_http_date(SystemTime::from(self.0));
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            res.headers_mut().insert(header::LAST_MODIFIED, value);
+        }
+        Ok(res)
+    }
+}
+
+/// Adds a strong ETag and a Cache-Control policy to successful GET and HEAD responses,
+/// and answers `If-None-Match` / `If-Modified-Since` with 304 Not Modified.
+#[derive(Debug, Clone)]
+pub struct HttpCacheLayer {
+    public: HeaderValue,
+    private: HeaderValue,
+}
+
+impl HttpCacheLayer {
+    pub fn new(public: &str, private: &str) -> Self {
+        Self {
+            public: parse_policy(public),
+            private: parse_policy(private),
+        }
+    }
+}
+
+fn parse_policy(policy: &str) -> HeaderValue {
+    HeaderValue::from_str(policy).unwrap_or_else(|_| {
+        tracing::warn!("Invalid Cache-Control policy {:?}, falling back to no-cache", policy);
+        HeaderValue::from_static("no-cache")
+    })
+}
+
+impl<S> Layer<S> for HttpCacheLayer {
+    type Service = HttpCache<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HttpCache {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpCache<S> {
+    inner: S,
+    layer: HttpCacheLayer,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for HttpCache<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+    ResBody: http_body::Body<Data = Bytes> + Send + 'static,
+    ResBody::Error: Into<BoxError>,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        let head = req.method() == Method::HEAD;
+        let cacheable = head || req.method() == Method::GET;
+        // Static files answer HEAD with an empty body, so HEAD is served as GET and the body
+        // dropped afterwards, giving it the ETag of the representation GET would return
+        if head {
+            *req.method_mut() = Method::GET;
+        }
+        let conditions = Conditions::from_headers(req.headers());
+        let layer = self.layer.clone();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let res = future.await?;
+            if !cacheable {
+                return Ok(res.map(Body::new));
+            }
+            let res = apply(res, &layer, &conditions).await;
+            if head {
+                let (parts, _) = res.into_parts();
+                return Ok(Response::from_parts(parts, Body::empty()));
+            }
+            Ok(res)
+        })
+    }
+}
+
+struct Conditions {
+    if_none_match: Option<HeaderValue>,
+    if_modified_since: Option<SystemTime>,
+}
+
+impl Conditions {
+    fn from_headers(headers: &HeaderMap) -> Self {
+        Self {
+            if_none_match: headers.get(header::IF_NONE_MATCH).cloned(),
+            if_modified_since: headers
+                .get(header::IF_MODIFIED_SINCE)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| httpdate::parse_http_date(v).ok()),
+        }
+    }
+
+    // RFC 9110 section 13.2.2: If-None-Match takes precedence over If-Modified-Since
+    fn not_modified(&self, headers: &HeaderMap) -> bool {
+        if let Some(if_none_match) = &self.if_none_match {
+            return match headers.get(header::ETAG) {
+                Some(etag) => etag_matches(if_none_match, etag),
+                None => false,
+            };
+        }
+
+        match (self.if_modified_since, last_modified(headers)) {
+            (Some(since), Some(modified)) => modified <= since,
+            _ => false,
+        }
+    }
+}
+
+fn last_modified(headers: &HeaderMap) -> Option<SystemTime> {
+    headers
+        .get(header::LAST_MODIFIED)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| httpdate::parse_http_date(v).ok())
+}
+
+// If-None-Match uses the weak comparison function, so W/ prefixes are ignored
+fn etag_matches(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let Ok(if_none_match) = if_none_match.to_str() else {
+        return false;
+    };
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+
+    if if_none_match.trim() == "*" {
+        return true;
+    }
+
+    let etag = etag.trim_start_matches("W/");
+    if_none_match
+        .split(',')
+        .map(|candidate| candidate.trim().trim_start_matches("W/"))
+        .any(|candidate| candidate == etag)
+}
+
+async fn apply<B>(res: Response<B>, layer: &HttpCacheLayer, conditions: &Conditions) -> Response<Body>
+where
+    B: http_body::Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    let (mut parts, body) = res.into_parts();
+
+    if parts.status != StatusCode::OK && parts.status != StatusCode::NOT_MODIFIED {
+        return Response::from_parts(parts, Body::new(body));
+    }
+
+    if !parts.headers.contains_key(header::CACHE_CONTROL) {
+        let policy = match parts.extensions.get::<Visibility>() {
+            Some(Visibility::Private) => layer.private.clone(),
+            _ => layer.public.clone(),
+        };
+        parts.headers.insert(header::CACHE_CONTROL, policy);
+    }
+
+    if parts.status == StatusCode::NOT_MODIFIED {
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    let body = if parts.headers.contains_key(header::ETAG) || !fits_in_memory(&parts.headers, &body) {
+        Body::new(body)
+    } else {
+        match axum::body::to_bytes(Body::new(body), MAX_ETAG_BODY_SIZE as usize).await {
+            Ok(bytes) => {
+                if let Ok(etag) = HeaderValue::from_str(&strong_etag(&bytes)) {
+                    parts.headers.insert(header::ETAG, etag);
+                }
+                Body::from(bytes)
+            }
+            Err(e) => {
+                tracing::error!("Failed to buffer response body for ETag: {}", e);
+                parts.status = StatusCode::INTERNAL_SERVER_ERROR;
+                return Response::from_parts(parts, Body::empty());
+            }
+        }
+    };
+
+    if conditions.not_modified(&parts.headers) {
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.remove(header::CONTENT_LENGTH);
+        parts.headers.remove(header::CONTENT_TYPE);
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    Response::from_parts(parts, body)
+}
+
+fn fits_in_memory<B: http_body::Body>(headers: &HeaderMap, body: &B) -> bool {
+    let content_length = headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .or_else(|| body.size_hint().exact());
+
+    matches!(content_length, Some(len) if len <= MAX_ETAG_BODY_SIZE)
+}
+
+fn strong_etag(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    format!("\"{}\"", hex::encode(&digest[..16]))
+}
@@ -0,0 +1,12 @@
+pub mod client_ip;
+pub mod cors;
+pub mod http_cache;
+pub mod rate_limit;
+pub mod request_id;
+pub mod security_headers;
+
+// Re-export layers for convenience
+pub use http_cache::HttpCacheLayer;
+pub use rate_limit::{InMemoryStore, RateLimitClient, RateLimiter};
+pub use request_id::RequestIdLayer;
+pub use security_headers::SecurityHeadersLayer;
\ No newline at end of file
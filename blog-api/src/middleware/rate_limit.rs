@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use axum::body::Body;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Request, Response};
+use axum::response::IntoResponse;
+use tower::{Layer, Service};
+use uuid::Uuid;
+
+use super::client_ip::TrustedProxies;
+use crate::api::auth::USER_ID_HEADER;
+use crate::config::{RateLimitConfig, RateLimitRule};
+use crate::errors::AppError;
+
+static RATELIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+static RATELIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+static RATELIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+
+// How often the in-memory store drops buckets that have refilled completely
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The outcome of taking a token from a bucket.
+#[derive(Debug, Clone, Copy)]
+pub struct Decision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    // Until the next token is available
+    pub retry_after: Duration,
+    // Until the bucket is full again
+    pub reset_after: Duration,
+}
+
+// Backend holding the token buckets. Keys already include the route group.
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    async fn acquire(&self, key: &str, rule: &RateLimitRule) -> anyhow::Result<Decision>;
+}
+
+/// Token buckets kept in process memory. Limits are per instance, so running several
+/// instances behind a load balancer multiplies the effective limit.
+#[derive(Default)]
+pub struct InMemoryStore {
+    state: Mutex<Buckets>,
+}
+
+#[derive(Default)]
+struct Buckets {
+    buckets: HashMap<String, Bucket>,
+    last_sweep: Option<Instant>,
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryStore {
+    async fn acquire(&self, key: &str, rule: &RateLimitRule) -> anyhow::Result<Decision> {
+        let now = Instant::now();
+        let capacity = f64::from(rule.burst);
+        let rate = f64::from(rule.requests) / rule.per_secs as f64;
+        let refilled = |bucket: &Bucket| {
+            let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+            (bucket.tokens + elapsed * rate).min(capacity)
+        };
+
+        let mut state = self.state.lock().unwrap();
+        if state.last_sweep.is_none_or(|last| now.duration_since(last) >= SWEEP_INTERVAL) {
+            // A full bucket behaves exactly like a missing one
+            state.buckets.retain(|_, bucket| refilled(bucket) < capacity);
+            state.last_sweep = Some(now);
+        }
+
+        let bucket = state.buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+        });
+        bucket.tokens = refilled(bucket);
+        bucket.updated_at = now;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        Ok(Decision {
+            allowed,
+            limit: rule.burst,
+            remaining: bucket.tokens.floor() as u32,
+            retry_after: Duration::from_secs_f64((1.0 - bucket.tokens).max(0.0) / rate),
+            reset_after: Duration::from_secs_f64((capacity - bucket.tokens) / rate),
+        })
+    }
+}
+
+/// Hands out a `RateLimitLayer` for each route group configured in `rate_limit.groups`.
+#[derive(Clone)]
+pub struct RateLimiter {
+    store: Arc<dyn RateLimitStore>,
+    proxies: Arc<TrustedProxies>,
+    groups: Arc<HashMap<String, RateLimitRule>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimitConfig, store: Arc<dyn RateLimitStore>) -> Self {
+        let groups = if config.enabled {
+            config
+                .groups
+                .iter()
+                .filter(|(name, rule)| {
+                    let valid = rule.requests > 0 && rule.per_secs > 0 && rule.burst > 0;
+                    if !valid {
+                        tracing::warn!("Rate limit group {:?} needs non-zero values, leaving it unlimited", name);
+                    }
+                    valid
+                })
+                .map(|(name, rule)| (name.clone(), rule.clone()))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        Self {
+            store,
+            proxies: Arc::new(TrustedProxies::new(&config.trusted_proxies)),
+            groups: Arc::new(groups),
+        }
+    }
+
+    // Limits each client IP, and each user as well. Groups missing from the configuration
+    // are not limited.
+    pub fn layer(&self, group: &str) -> RateLimitLayer {
+        self.build(group, true)
+    }
+
+    // Limits each client IP only, for routes used before there is a user, like signing up
+    pub fn ip_layer(&self, group: &str) -> RateLimitLayer {
+        self.build(group, false)
+    }
+
+    fn build(&self, group: &str, per_user: bool) -> RateLimitLayer {
+        RateLimitLayer {
+            group: group.into(),
+            rule: self.groups.get(group).cloned().map(Arc::new),
+            per_user,
+            store: self.store.clone(),
+            proxies: self.proxies.clone(),
+        }
+    }
+}
+
+/// Throttles requests with a token bucket per client IP and, for requests naming a user,
+/// a second bucket per user, and reports the bucket state in `RateLimit-*` headers. Layers
+/// may be nested; the headers then describe whichever bucket has the fewest requests left.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    group: Arc<str>,
+    rule: Option<Arc<RateLimitRule>>,
+    per_user: bool,
+    store: Arc<dyn RateLimitStore>,
+    proxies: Arc<TrustedProxies>,
+}
+
+/// Who a request came from, as far as rate limiting is concerned. `RateLimitLayer` adds it
+/// to the request extensions, so work done inside a request, like a GraphQL mutation, can
+/// be charged against another group with `RateLimitLayer::check`.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitClient {
+    ip: Option<IpAddr>,
+    user: Option<Uuid>,
+}
+
+impl RateLimitLayer {
+    fn client<B>(&self, req: &Request<B>) -> RateLimitClient {
+        let user = req
+            .headers()
+            .get(USER_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| Uuid::parse_str(v.trim()).ok());
+        RateLimitClient {
+            ip: self.proxies.client_ip(req),
+            user,
+        }
+    }
+
+    fn keys(&self, client: &RateLimitClient) -> Vec<String> {
+        let mut keys = Vec::with_capacity(2);
+        // X-User-Id is not verified here, so the IP bucket always applies: sending a new
+        // id with every request must not get a client a fresh bucket
+        if let Some(ip) = client.ip {
+            keys.push(format!("{}:ip:{}", self.group, ip));
+        }
+        if let Some(user) = client.user.filter(|_| self.per_user) {
+            keys.push(format!("{}:user:{}", self.group, user));
+        }
+        keys
+    }
+
+    /// Takes a token for `client` without going through the layer, failing with
+    /// `TooManyRequests` once a bucket is empty.
+    pub async fn check(&self, client: &RateLimitClient) -> Result<(), AppError> {
+        let Some(rule) = self.rule.as_deref() else {
+            return Ok(());
+        };
+
+        match self.acquire(&self.keys(client), rule).await {
+            Ok(Some(decision)) if !decision.allowed => Err(AppError::TooManyRequests(ceil_secs(decision.retry_after))),
+            Ok(_) => Ok(()),
+            Err(e) => {
+                tracing::error!("Rate limit store failed for group {}: {}", self.group, e);
+                Ok(())
+            }
+        }
+    }
+
+    // Takes a token from every bucket of the request
+    async fn acquire(&self, keys: &[String], rule: &RateLimitRule) -> anyhow::Result<Option<Decision>> {
+        let mut combined: Option<Decision> = None;
+        for key in keys {
+            let decision = self.store.acquire(key, rule).await?;
+            combined = Some(combined.map_or(decision, |current| tighter(current, decision)));
+        }
+        Ok(combined)
+    }
+}
+
+// The decision to report for two buckets: the one refusing the request, or else the one
+// with the fewest requests left
+fn tighter(a: Decision, b: Decision) -> Decision {
+    match (a.allowed, b.allowed) {
+        (true, false) => b,
+        (false, true) => a,
+        (false, false) if b.retry_after > a.retry_after => b,
+        (true, true) if b.remaining < a.remaining => b,
+        _ => a,
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimit<S> {
+    inner: S,
+    layer: RateLimitLayer,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for RateLimit<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+    ResBody: Into<Body>,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        let client = self.layer.client(&req);
+        req.extensions_mut().insert(client);
+
+        // The service that was polled ready is the one that has to handle the request
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+
+        Box::pin(async move {
+            let Some(rule) = layer.rule.as_deref() else {
+                return Ok(inner.call(req).await?.map(Into::into));
+            };
+
+            let decision = match layer.acquire(&layer.keys(&client), rule).await {
+                Ok(Some(decision)) => decision,
+                Ok(None) => return Ok(inner.call(req).await?.map(Into::into)),
+                Err(e) => {
+                    // Fail open so an unavailable backend does not take the API down
+                    tracing::error!("Rate limit store failed for group {}: {}", layer.group, e);
+                    return Ok(inner.call(req).await?.map(Into::into));
+                }
+            };
+
+            let mut res = if decision.allowed {
+                inner.call(req).await?.map(Into::into)
+            } else {
+                AppError::TooManyRequests(ceil_secs(decision.retry_after)).into_response()
+            };
+            set_headers(res.headers_mut(), &decision);
+            Ok(res)
+        })
+    }
+}
+
+fn set_headers(headers: &mut HeaderMap, decision: &Decision) {
+    // Keep the headers of an inner layer that is closer to its limit
+    let inner_remaining = headers
+        .get(&RATELIMIT_REMAINING)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u32>().ok());
+    if inner_remaining.is_some_and(|remaining| remaining <= decision.remaining) {
+        return;
+    }
+
+    headers.insert(RATELIMIT_LIMIT.clone(), HeaderValue::from(decision.limit));
+    headers.insert(RATELIMIT_REMAINING.clone(), HeaderValue::from(decision.remaining));
+    headers.insert(RATELIMIT_RESET.clone(), HeaderValue::from(ceil_secs(decision.reset_after)));
+}
+
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs_f64().ceil() as u64
+}
\ No newline at end of file
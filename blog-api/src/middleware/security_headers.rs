@@ -0,0 +1,105 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum::http::{header, HeaderName, HeaderValue, Request, Response};
+use tower::{Layer, Service};
+
+use crate::config::SecurityHeadersConfig;
+
+// The static web UI, the only part of the site the Content-Security-Policy is written for
+const UI_PREFIX: &str = "/ui";
+
+/// Adds the configured security headers to every response, leaving alone any header the
+/// handler has already set. The Content-Security-Policy only goes on the web UI, since the
+/// Swagger UI and GraphiQL pages rely on inline scripts.
+#[derive(Debug, Clone)]
+pub struct SecurityHeadersLayer {
+    headers: Arc<[(HeaderName, HeaderValue)]>,
+    ui_policy: Option<HeaderValue>,
+}
+
+impl SecurityHeadersLayer {
+    pub fn new(config: &SecurityHeadersConfig) -> Self {
+        let headers = [
+            (header::STRICT_TRANSPORT_SECURITY, &config.strict_transport_security),
+            (header::X_CONTENT_TYPE_OPTIONS, &config.content_type_options),
+            (header::REFERRER_POLICY, &config.referrer_policy),
+            (header::X_FRAME_OPTIONS, &config.frame_options),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.clone(), parse_value(&name, value.as_deref()?)?)))
+        .collect();
+
+        let ui_policy = config
+            .ui_content_security_policy
+            .as_deref()
+            .and_then(|value| parse_value(&header::CONTENT_SECURITY_POLICY, value));
+
+        Self { headers, ui_policy }
+    }
+}
+
+fn parse_value(name: &HeaderName, value: &str) -> Option<HeaderValue> {
+    let parsed = HeaderValue::from_str(value).ok();
+    if parsed.is_none() {
+        tracing::warn!("Ignoring invalid {} header value {:?}", name, value);
+    }
+    parsed
+}
+
+impl<S> Layer<S> for SecurityHeadersLayer {
+    type Service = SecurityHeaders<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SecurityHeaders {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SecurityHeaders<S> {
+    inner: S,
+    layer: SecurityHeadersLayer,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for SecurityHeaders<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let ui = req
+            .uri()
+            .path()
+            .strip_prefix(UI_PREFIX)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'));
+        let layer = self.layer.clone();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let mut res = future.await?;
+            let headers = res.headers_mut();
+
+            for (name, value) in layer.headers.iter() {
+                headers.entry(name.clone()).or_insert_with(|| value.clone());
+            }
+            if let Some(policy) = layer.ui_policy.filter(|_| ui) {
+                headers.entry(header::CONTENT_SECURITY_POLICY).or_insert(policy);
+            }
+
+            Ok(res)
+        })
+    }
+}
\ No newline at end of file
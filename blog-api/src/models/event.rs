@@ -0,0 +1,30 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::post::Post;
+
+// The post fields sent with live events. Content is left out to stay well under the
+// NOTIFY payload limit; clients fetch the post when they need it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostEventData {
+    pub id: Uuid,
+    pub title: String,
+    pub author_id: Uuid,
+    pub published: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&Post> for PostEventData {
+    fn from(post: &Post) -> Self {
+        Self {
+            id: post.id,
+            title: post.title.clone(),
+            author_id: post.author_id,
+            published: post.published,
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,10 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct FollowCounts {
+    pub user_id: Uuid,
+    pub follower_count: i64,
+    pub following_count: i64,
+}
\ No newline at end of file
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_PROCESSING: &str = "processing";
+pub const STATUS_READY: &str = "ready";
+pub const STATUS_FAILED: &str = "failed";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Image {
+    pub id: Uuid,
+    pub uploader_id: Uuid,
+    pub status: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ImageVariant {
+    pub image_id: Uuid,
+    pub name: String,
+    pub format: String,
+    pub width: i32,
+    pub height: i32,
+    pub size_bytes: i64,
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+}
+
+impl ImageVariant {
+    pub fn url(&self) -> String {
+        format!("/images/{}/{}.{}", self.image_id, self.name, file_extension(&self.format))
+    }
+}
+
+pub fn file_extension(format: &str) -> &str {
+    match format {
+        "jpeg" => "jpg",
+        other => other,
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImageVariantResponse {
+    pub name: String,
+    pub format: String,
+    pub width: i32,
+    pub height: i32,
+    pub size_bytes: i64,
+    pub url: String,
+}
+
+impl From<ImageVariant> for ImageVariantResponse {
+    fn from(variant: ImageVariant) -> Self {
+        Self {
+            url: variant.url(),
+            name: variant.name,
+            format: variant.format,
+            width: variant.width,
+            height: variant.height,
+            size_bytes: variant.size_bytes,
+        }
+    }
+}
+
+// Ready-made `srcset` attribute values per format, e.g. "/images/<id>/w320.webp 320w, ..."
+#[derive(Debug, Default, Serialize)]
+pub struct SrcSet {
+    pub webp: String,
+    pub jpeg: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImageResponse {
+    pub id: Uuid,
+    pub uploader_id: Uuid,
+    pub status: String,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub error: Option<String>,
+    pub srcset: SrcSet,
+    pub variants: Vec<ImageVariantResponse>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ImageResponse {
+    pub fn new(image: Image, variants: Vec<ImageVariant>) -> Self {
+        let srcset_for = |format: &str| {
+            variants
+                .iter()
+                .filter(|v| v.format == format && v.name != "thumb")
+                .map(|v| format!("{} {}w", v.url(), v.width))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let srcset = SrcSet {
+            webp: srcset_for("webp"),
+            jpeg: srcset_for("jpeg"),
+        };
+
+        Self {
+            id: image.id,
+            uploader_id: image.uploader_id,
+            status: image.status,
+            width: image.width,
+            height: image.height,
+            error: image.error,
+            srcset,
+            variants: variants.into_iter().map(ImageVariantResponse::from).collect(),
+            created_at: image.created_at,
+        }
+    }
+}
@@ -1,2 +1,12 @@
 pub mod user;
-pub mod post;
\ No newline at end of file
+pub mod post;
+pub mod media;
+pub mod image;
+pub mod reaction;
+pub mod read;
+pub mod reading_list;
+pub mod follow;
+pub mod notification;
+pub mod webhook;
+pub mod event;
+pub mod v2;
\ No newline at end of file
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+pub const KIND_FOLLOW: &str = "follow";
+pub const KIND_REACTION: &str = "reaction";
+pub const KIND_MENTION: &str = "mention";
+
+pub const KINDS: [&str; 3] = [KIND_FOLLOW, KIND_REACTION, KIND_MENTION];
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Notification {
+    pub id: Uuid,
+    pub recipient_id: Uuid,
+    pub kind: String,
+    pub post_id: Option<Uuid>,
+    pub group_key: String,
+    pub actor_ids: Vec<Uuid>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationActor {
+    pub id: Uuid,
+    pub username: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationResponse {
+    pub id: Uuid,
+    pub kind: String,
+    pub post_id: Option<Uuid>,
+    // The most recent actors, up to three
+    pub actors: Vec<NotificationActor>,
+    pub actor_count: usize,
+    pub message: String,
+    pub read: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationListResponse {
+    pub unread_count: i64,
+    pub notifications: Vec<NotificationResponse>,
+}
\ No newline at end of file
@@ -2,8 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
+use utoipa::ToSchema;
 use validator::Validate;
 
+use crate::models::reaction::ReactionSummary;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Post {
     pub id: Uuid,
@@ -15,25 +18,29 @@ pub struct Post {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreatePostRequest {
     #[validate(length(min = 3, max = 100))]
+    #[schema(min_length = 3, max_length = 100)]
     pub title: String,
     #[validate(length(min = 10))]
+    #[schema(min_length = 10)]
     pub content: String,
     pub published: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdatePostRequest {
     #[validate(length(min = 3, max = 100))]
+    #[schema(min_length = 3, max_length = 100)]
     pub title: Option<String>,
     #[validate(length(min = 10))]
+    #[schema(min_length = 10)]
     pub content: Option<String>,
     pub published: Option<bool>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PostResponse {
     pub id: Uuid,
     pub title: String,
@@ -42,6 +49,12 @@ pub struct PostResponse {
     pub published: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub reactions: Vec<ReactionSummary>,
+    // Per-user state, only present when the request identifies a user
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub my_reactions: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read: Option<bool>,
 }
 
 impl From<Post> for PostResponse {
@@ -54,6 +67,16 @@ impl From<Post> for PostResponse {
             published: post.published,
             created_at: post.created_at,
             updated_at: post.updated_at,
+            reactions: Vec::new(),
+            my_reactions: None,
+            read: None,
         }
     }
+}
+
+// A page of a cursor-paginated listing. Pass `next_cursor` back as `?cursor=` for the next page.
+#[derive(Debug, Serialize)]
+pub struct PostPage {
+    pub posts: Vec<PostResponse>,
+    pub next_cursor: Option<String>,
 }
\ No newline at end of file
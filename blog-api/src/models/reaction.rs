@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReactionCount {
+    pub post_id: Uuid,
+    pub kind: String,
+    pub count: i64,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReactionSummary {
+    pub kind: String,
+    pub emoji: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReactionsResponse {
+    pub post_id: Uuid,
+    pub reactions: Vec<ReactionSummary>,
+    pub my_reactions: Vec<String>,
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReadStatusResponse {
+    pub post_id: Uuid,
+    pub read: bool,
+    pub read_at: Option<DateTime<Utc>>,
+}
+
+// Marks the given posts as read, or every published post with `all: true`
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct MarkReadRequest {
+    #[serde(default)]
+    #[validate(length(max = 1000))]
+    #[schema(max_items = 1000)]
+    pub post_ids: Vec<Uuid>,
+    #[serde(default)]
+    pub all: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MarkReadResponse {
+    pub marked: u64,
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReadingList {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub shared: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ReadingListSummary {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub shared: bool,
+    pub item_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// A list entry joined with the post it points at
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct ReadingListItem {
+    pub post_id: Uuid,
+    pub title: String,
+    pub author_id: Uuid,
+    pub published: bool,
+    pub position: i32,
+    pub added_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateReadingListRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    pub shared: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateReadingListRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    pub shared: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddReadingListItemRequest {
+    pub post_id: Uuid,
+}
+
+// The complete new order of a list's items, first to last
+#[derive(Debug, Deserialize)]
+pub struct ReorderReadingListRequest {
+    pub post_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadingListResponse {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub shared: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub items: Vec<ReadingListItem>,
+}
+
+impl ReadingListResponse {
+    pub fn new(list: ReadingList, items: Vec<ReadingListItem>) -> Self {
+        Self {
+            id: list.id,
+            owner_id: list.owner_id,
+            name: list.name,
+            description: list.description,
+            shared: list.shared,
+            created_at: list.created_at,
+            updated_at: list.updated_at,
+            items,
+        }
+    }
+}
\ No newline at end of file
@@ -2,6 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
+use utoipa::ToSchema;
 use validator::Validate;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -15,23 +16,29 @@ pub struct User {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreateUserRequest {
     #[validate(length(min = 3, max = 50))]
+    #[schema(min_length = 3, max_length = 50)]
     pub username: String,
     #[validate(email)]
+    #[schema(format = Email)]
     pub email: String,
     #[validate(length(min = 8))]
+    #[schema(min_length = 8, format = Password)]
     pub password: String,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct UpdateUserRequest {
     #[validate(length(min = 3, max = 50))]
+    #[schema(min_length = 3, max_length = 50)]
     pub username: Option<String>,
     #[validate(email)]
+    #[schema(format = Email)]
     pub email: Option<String>,
     #[validate(length(min = 8))]
+    #[schema(min_length = 8, format = Password)]
     pub password: Option<String>,
 }
 
@@ -43,12 +50,14 @@ pub struct LoginRequest {
     pub password: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct UserResponse {
     pub id: Uuid,
     pub username: String,
     pub email: String,
     pub created_at: DateTime<Utc>,
+    pub follower_count: i64,
+    pub following_count: i64,
 }
 
 impl From<User> for UserResponse {
@@ -58,6 +67,8 @@ impl From<User> for UserResponse {
             username: user.username,
             email: user.email,
             created_at: user.created_at,
+            follower_count: 0,
+            following_count: 0,
         }
     }
 }
\ No newline at end of file
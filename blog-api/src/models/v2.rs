@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::post;
+use crate::models::reaction::ReactionSummary;
+use crate::models::user::User;
+
+// Response shapes of the v2 API. They wrap listings in a pagination envelope and embed
+// authors in posts, but are built from the same repositories as v1.
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Author {
+    pub id: Uuid,
+    pub username: String,
+}
+
+impl From<User> for Author {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostResponse {
+    pub id: Uuid,
+    pub title: String,
+    pub content: String,
+    pub author: Author,
+    pub published: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub reactions: Vec<ReactionSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub my_reactions: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read: Option<bool>,
+}
+
+impl PostResponse {
+    pub fn new(post: post::PostResponse, author: Author) -> Self {
+        Self {
+            id: post.id,
+            title: post.title,
+            content: post.content,
+            author,
+            published: post.published,
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+            reactions: post.reactions,
+            my_reactions: post.my_reactions,
+            read: post.read,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub data: Vec<T>,
+    pub pagination: PageInfo,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PageInfo {
+    pub limit: i64,
+    pub offset: i64,
+    // Pass as `?offset=` for the next page; absent on the last page
+    pub next_offset: Option<i64>,
+}
+
+impl<T> Page<T> {
+    // Expects up to `limit + 1` items, the extra one only telling whether there is a next page
+    pub fn new(mut data: Vec<T>, limit: i64, offset: i64) -> Self {
+        let has_more = data.len() as i64 > limit;
+        data.truncate(limit.max(0) as usize);
+
+        Self {
+            data,
+            pagination: PageInfo {
+                limit,
+                offset,
+                next_offset: has_more.then_some(offset + limit),
+            },
+        }
+    }
+}
\ No newline at end of file
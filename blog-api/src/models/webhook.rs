@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+pub const EVENT_POST_CREATED: &str = "post.created";
+pub const EVENT_POST_UPDATED: &str = "post.updated";
+pub const EVENT_POST_PUBLISHED: &str = "post.published";
+pub const EVENT_POST_DELETED: &str = "post.deleted";
+pub const EVENT_USER_CREATED: &str = "user.created";
+// Sent on request to check that a receiver is reachable
+pub const EVENT_PING: &str = "ping";
+
+pub const EVENTS: [&str; 5] = [
+    EVENT_POST_CREATED,
+    EVENT_POST_UPDATED,
+    EVENT_POST_PUBLISHED,
+    EVENT_POST_DELETED,
+    EVENT_USER_CREATED,
+];
+
+pub const STATUS_PENDING: &str = "pending";
+pub const STATUS_SUCCEEDED: &str = "succeeded";
+pub const STATUS_FAILED: &str = "failed";
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub url: String,
+    pub events: Vec<String>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDeliveryAttempt {
+    pub id: Uuid,
+    pub delivery_id: Uuid,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub duration_ms: i32,
+    pub attempted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateWebhookRequest {
+    #[validate(url)]
+    pub url: String,
+    #[validate(length(min = 1))]
+    pub events: Vec<String>,
+    // Generated when omitted
+    #[validate(length(min = 16, max = 128))]
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateWebhookRequest {
+    #[validate(url)]
+    pub url: Option<String>,
+    #[validate(length(min = 1))]
+    pub events: Option<Vec<String>>,
+    pub active: Option<bool>,
+}
+
+// Returned once, when the subscription is created, so the secret can be stored by the receiver
+#[derive(Debug, Serialize)]
+pub struct CreatedWebhookResponse {
+    #[serde(flatten)]
+    pub subscription: WebhookSubscription,
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebhookDeliveryResponse {
+    #[serde(flatten)]
+    pub delivery: WebhookDelivery,
+    pub attempt_log: Vec<WebhookDeliveryAttempt>,
+}
\ No newline at end of file
@@ -0,0 +1,132 @@
+use std::collections::BTreeSet;
+
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::db::notification_repository::NewNotification;
+use crate::db::{NotificationRepository, PostRepository, UserRepository};
+use crate::errors::Result;
+use crate::models::notification::{KIND_FOLLOW, KIND_MENTION, KIND_REACTION};
+use crate::shutdown::Shutdown;
+
+// Domain events that can notify someone
+#[derive(Debug, Clone)]
+pub enum Event {
+    Followed { follower_id: Uuid, followee_id: Uuid },
+    Reacted { user_id: Uuid, post_id: Uuid },
+    // Usernames newly mentioned in a published post
+    Mentioned { author_id: Uuid, post_id: Uuid, usernames: Vec<String> },
+}
+
+// Turns domain events into notifications on a background task, so request handlers
+// never wait on, or fail because of, notification bookkeeping.
+#[derive(Clone)]
+pub struct Notifier {
+    sender: mpsc::UnboundedSender<Event>,
+}
+
+impl Notifier {
+    pub fn start(pool: PgPool, shutdown: &Shutdown) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Event>();
+        let token = shutdown.token();
+
+        shutdown.spawn(async move {
+            loop {
+                let event = tokio::select! {
+                    event = receiver.recv() => event,
+                    // Refuse new events but still handle the ones already queued
+                    _ = token.cancelled(), if !token.is_cancelled() => {
+                        receiver.close();
+                        continue;
+                    }
+                };
+                let Some(event) = event else { break };
+                if let Err(e) = generate(&pool, &event).await {
+                    tracing::error!("Failed to create notifications for {:?}: {}", event, e);
+                }
+            }
+        });
+
+        Self { sender }
+    }
+
+    pub fn notify(&self, event: Event) {
+        if self.sender.send(event).is_err() {
+            tracing::error!("Notification generator is not running");
+        }
+    }
+}
+
+async fn generate(pool: &PgPool, event: &Event) -> Result<()> {
+    let repo = NotificationRepository::new(pool.clone());
+
+    match event {
+        Event::Followed { follower_id, followee_id } => {
+            repo.record(&NewNotification {
+                recipient_id: *followee_id,
+                kind: KIND_FOLLOW,
+                post_id: None,
+                group_key: KIND_FOLLOW.to_string(),
+                actor_id: *follower_id,
+            })
+            .await?;
+        }
+        Event::Reacted { user_id, post_id } => {
+            let post = match PostRepository::new(pool.clone()).find_by_id(*post_id).await? {
+                Some(post) => post,
+                None => return Ok(()),
+            };
+            if post.author_id == *user_id {
+                return Ok(());
+            }
+            repo.record(&NewNotification {
+                recipient_id: post.author_id,
+                kind: KIND_REACTION,
+                post_id: Some(post.id),
+                group_key: format!("{}:{}", KIND_REACTION, post.id),
+                actor_id: *user_id,
+            })
+            .await?;
+        }
+        Event::Mentioned { author_id, post_id, usernames } => {
+            let users = UserRepository::new(pool.clone()).find_by_usernames(usernames).await?;
+            for user in users.iter().filter(|u| u.id != *author_id) {
+                repo.record(&NewNotification {
+                    recipient_id: user.id,
+                    kind: KIND_MENTION,
+                    post_id: Some(*post_id),
+                    group_key: format!("{}:{}", KIND_MENTION, post_id),
+                    actor_id: *author_id,
+                })
+                .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Extracts the usernames mentioned as @name in a text, without duplicates.
+// A mention has to start the text or follow a character that can't be part of a name,
+// so e-mail addresses don't count.
+pub fn mentions(text: &str) -> BTreeSet<String> {
+    let is_name_char = |c: char| c.is_alphanumeric() || c == '_' || c == '-' || c == '.';
+    let mut found = BTreeSet::new();
+    let mut previous = None;
+
+    for (i, c) in text.char_indices() {
+        if c == '@' && !previous.is_some_and(is_name_char) {
+            let rest = &text[i + 1..];
+            let end = rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len());
+            // Trailing dots end a sentence rather than the name
+            let name = rest[..end].trim_end_matches('.');
+            if (3..=50).contains(&name.chars().count()) {
+                found.insert(name.to_string());
+            }
+        }
+        previous = Some(c);
+    }
+
+    found
+}
\ No newline at end of file
@@ -0,0 +1,249 @@
+use std::io::{self, Cursor};
+use std::path::PathBuf;
+
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+use chrono::{DateTime, Utc};
+use image::{ImageFormat, Rgba, RgbaImage};
+use sha2::{Digest, Sha256};
+use tokio::fs;
+use uuid::Uuid;
+
+use crate::config::OgImageConfig;
+
+pub const WIDTH: u32 = 1200;
+pub const HEIGHT: u32 = 630;
+
+const PADDING: f32 = 80.0;
+const ACCENT_HEIGHT: u32 = 16;
+const SITE_SIZE: f32 = 36.0;
+const TITLE_SIZE: f32 = 72.0;
+const TITLE_LINE_HEIGHT: f32 = 86.0;
+const TITLE_MAX_LINES: usize = 4;
+const META_SIZE: f32 = 36.0;
+
+static REGULAR_FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans.ttf");
+static BOLD_FONT: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans-Bold.ttf");
+
+// The post details that end up on the card
+pub struct Card<'a> {
+    pub title: &'a str,
+    pub author: &'a str,
+    pub published: DateTime<Utc>,
+}
+
+#[derive(Clone, Copy)]
+struct Palette {
+    background: Rgba<u8>,
+    text: Rgba<u8>,
+    muted: Rgba<u8>,
+    accent: Rgba<u8>,
+}
+
+// Renders OpenGraph cards and keeps them on disk, keyed by post id and updated_at
+pub struct OgImages {
+    cache_dir: PathBuf,
+    site_name: String,
+    palette: Palette,
+    // Changes whenever the theme or site name does, so cached cards never go stale
+    theme_key: String,
+}
+
+impl OgImages {
+    pub fn new(config: &OgImageConfig, site_name: &str) -> Self {
+        let theme = &config.theme;
+        let digest = Sha256::digest(format!("{:?}{}", theme, site_name).as_bytes());
+
+        Self {
+            cache_dir: PathBuf::from(&config.cache_dir),
+            site_name: site_name.to_string(),
+            palette: Palette {
+                background: parse_color(&theme.background, [15, 23, 42]),
+                text: parse_color(&theme.text, [248, 250, 252]),
+                muted: parse_color(&theme.muted, [148, 163, 184]),
+                accent: parse_color(&theme.accent, [52, 152, 219]),
+            },
+            theme_key: hex::encode(&digest[..4]),
+        }
+    }
+
+    // Returns the cached PNG for this version of the post, rendering it on a miss. Each post
+    // has its own directory, `<cache_dir>/<post_id>/<updated_at>-<theme>.png`.
+    pub async fn get_or_render(&self, post_id: Uuid, updated_at: DateTime<Utc>, card: Card<'_>) -> io::Result<Vec<u8>> {
+        let dir = self.cache_dir.join(post_id.to_string());
+        let file_name = format!("{}-{}.png", updated_at.timestamp_micros(), self.theme_key);
+        let path = dir.join(&file_name);
+
+        match fs::read(&path).await {
+            Ok(png) => return Ok(png),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        let palette = self.palette;
+        let site_name = self.site_name.clone();
+        let title = card.title.to_string();
+        let meta = format!("{}  ·  {}", card.author, card.published.format("%B %-d, %Y"));
+        let png = tokio::task::spawn_blocking(move || render(palette, &site_name, &title, &meta))
+            .await
+            .map_err(io::Error::other)??;
+
+        // Concurrent renders of the same card each write their own temporary file. The leading
+        // dot keeps it out of the cleanup below.
+        fs::create_dir_all(&dir).await?;
+        let tmp_path = dir.join(format!(".{}.{}.tmp", file_name, Uuid::new_v4()));
+        fs::write(&tmp_path, &png).await?;
+        if let Err(e) = fs::rename(&tmp_path, &path).await {
+            let _ = fs::remove_file(&tmp_path).await;
+            // Another request cached the same card first, which is as good as a hit
+            if fs::try_exists(&path).await.unwrap_or(false) {
+                return Ok(png);
+            }
+            return Err(e);
+        }
+
+        // Older versions of this post's card are never served again
+        let mut entries = fs::read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if !name.starts_with('.') && name != file_name {
+                let _ = fs::remove_file(entry.path()).await;
+            }
+        }
+
+        Ok(png)
+    }
+}
+
+fn parse_color(value: &str, fallback: [u8; 3]) -> Rgba<u8> {
+    let hex = value.trim_start_matches('#');
+    let parsed = (hex.len() == 6)
+        .then(|| u32::from_str_radix(hex, 16).ok())
+        .flatten()
+        .map(|rgb| [(rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8]);
+
+    let [r, g, b] = parsed.unwrap_or_else(|| {
+        tracing::warn!("Invalid theme color {:?}, using the default", value);
+        fallback
+    });
+    Rgba([r, g, b, 255])
+}
+
+fn render(palette: Palette, site_name: &str, title: &str, meta: &str) -> io::Result<Vec<u8>> {
+    let regular = FontRef::try_from_slice(REGULAR_FONT).map_err(io::Error::other)?;
+    let bold = FontRef::try_from_slice(BOLD_FONT).map_err(io::Error::other)?;
+
+    let mut canvas = RgbaImage::from_pixel(WIDTH, HEIGHT, palette.background);
+    for y in HEIGHT - ACCENT_HEIGHT..HEIGHT {
+        for x in 0..WIDTH {
+            canvas.put_pixel(x, y, palette.accent);
+        }
+    }
+
+    let max_width = WIDTH as f32 - 2.0 * PADDING;
+    draw_text(&mut canvas, &regular, SITE_SIZE, PADDING, PADDING + SITE_SIZE, site_name, palette.muted);
+
+    let lines = wrap(&bold, TITLE_SIZE, title, max_width, TITLE_MAX_LINES);
+    let mut baseline = PADDING + SITE_SIZE + 40.0 + TITLE_SIZE;
+    for line in &lines {
+        draw_text(&mut canvas, &bold, TITLE_SIZE, PADDING, baseline, line, palette.text);
+        baseline += TITLE_LINE_HEIGHT;
+    }
+
+    let meta_baseline = (HEIGHT - ACCENT_HEIGHT) as f32 - PADDING + META_SIZE / 2.0;
+    draw_text(&mut canvas, &regular, META_SIZE, PADDING, meta_baseline, meta, palette.muted);
+
+    let mut png = Vec::new();
+    canvas
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(io::Error::other)?;
+    Ok(png)
+}
+
+fn text_width(font: &FontRef, size: f32, text: &str) -> f32 {
+    let scaled = font.as_scaled(PxScale::from(size));
+    let mut width = 0.0;
+    let mut previous = None;
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            width += scaled.kern(previous, id);
+        }
+        width += scaled.h_advance(id);
+        previous = Some(id);
+    }
+    width
+}
+
+// Greedy word wrap; the last allowed line gets an ellipsis if text is left over
+fn wrap(font: &FontRef, size: f32, text: &str, max_width: f32, max_lines: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() { word.to_string() } else { format!("{} {}", current, word) };
+        if current.is_empty() || text_width(font, size, &candidate) <= max_width {
+            current = candidate;
+            continue;
+        }
+        lines.push(std::mem::replace(&mut current, word.to_string()));
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        let last = &mut lines[max_lines - 1];
+        while !last.is_empty() && text_width(font, size, &format!("{}…", last)) > max_width {
+            last.pop();
+        }
+        last.push('…');
+    }
+
+    // A single word wider than the card is cut rather than overflowing
+    for line in &mut lines {
+        while text_width(font, size, line) > max_width && line.chars().count() > 1 {
+            line.pop();
+            line.pop();
+            line.push('…');
+        }
+    }
+
+    lines
+}
+
+fn draw_text(canvas: &mut RgbaImage, font: &FontRef, size: f32, x: f32, baseline: f32, text: &str, color: Rgba<u8>) {
+    let scale = PxScale::from(size);
+    let scaled = font.as_scaled(scale);
+    let mut caret = x;
+    let mut previous = None;
+
+    for c in text.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(previous) = previous {
+            caret += scaled.kern(previous, id);
+        }
+        let glyph = id.with_scale_and_position(scale, point(caret, baseline));
+        caret += scaled.h_advance(id);
+        previous = Some(id);
+
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            continue;
+        };
+        let bounds = outlined.px_bounds();
+        outlined.draw(|gx, gy, coverage| {
+            let px = bounds.min.x as i32 + gx as i32;
+            let py = bounds.min.y as i32 + gy as i32;
+            if px < 0 || py < 0 || px >= WIDTH as i32 || py >= HEIGHT as i32 {
+                return;
+            }
+            let pixel = canvas.get_pixel_mut(px as u32, py as u32);
+            let coverage = coverage.clamp(0.0, 1.0);
+            for i in 0..3 {
+                let blended = color[i] as f32 * coverage + pixel[i] as f32 * (1.0 - coverage);
+                pixel[i] = blended.round() as u8;
+            }
+        });
+    }
+}
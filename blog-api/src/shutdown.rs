@@ -0,0 +1,99 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+
+/// Shared shutdown state. Shutdown goes through three stages: readiness checks fail so load
+/// balancers stop routing new traffic here, then the listener closes and in-flight requests
+/// finish, and finally background tasks are cancelled and waited for.
+#[derive(Debug, Clone, Default)]
+pub struct Shutdown {
+    draining: Arc<AtomicBool>,
+    // Cancelled when the listener closes, to end long-lived responses such as SSE streams
+    closing: CancellationToken,
+    // Cancelled once requests are done, or have run out of time
+    stopping: CancellationToken,
+    tasks: TaskTracker,
+}
+
+impl Shutdown {
+    pub fn begin_draining(&self) {
+        self.draining.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+
+    // Waits for SIGINT or SIGTERM, then fails readiness for `delay` before resolving. Pass
+    // it to `with_graceful_shutdown`, which stops accepting connections when it resolves.
+    pub async fn triggered(self, delay: Duration) {
+        signal().await;
+        self.begin_draining();
+        tracing::info!("Readiness now failing, closing listener in {}s", delay.as_secs());
+        tokio::time::sleep(delay).await;
+
+        tracing::info!("Closing listener and draining in-flight requests");
+        self.closing.cancel();
+    }
+
+    pub fn closing(&self) -> CancellationToken {
+        self.closing.clone()
+    }
+
+    /// Token cancelled when background tasks should finish their current work and exit.
+    pub fn token(&self) -> CancellationToken {
+        self.stopping.clone()
+    }
+
+    /// Spawns a background task that `stop_tasks` waits for.
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks.spawn(task);
+    }
+
+    // Cancels background tasks and waits for them until `deadline`. Returns false if some
+    // were still running, in which case they are dropped when the runtime shuts down.
+    pub async fn stop_tasks(&self, deadline: Instant) -> bool {
+        self.stopping.cancel();
+        self.tasks.close();
+        tokio::time::timeout_at(deadline, self.tasks.wait()).await.is_ok()
+    }
+}
+
+// Resolves on SIGINT (Ctrl+C) or, on Unix, SIGTERM
+async fn signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Received SIGINT"),
+        _ = terminate => tracing::info!("Received SIGTERM"),
+    }
+}
\ No newline at end of file
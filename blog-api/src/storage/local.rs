@@ -0,0 +1,54 @@
+use std::io::{self, SeekFrom};
+use std::ops::Range;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use axum::body::{Body, Bytes};
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
+
+use super::MediaStore;
+
+// Stores each media file as a flat file in a local directory
+pub struct LocalMediaStore {
+    root: PathBuf,
+}
+
+impl LocalMediaStore {
+    pub async fn new(root: impl Into<PathBuf>) -> io::Result<Self> {
+        let root = root.into();
+        fs::create_dir_all(&root).await?;
+        Ok(Self { root })
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+}
+
+#[async_trait]
+impl MediaStore for LocalMediaStore {
+    async fn put(&self, key: &str, data: Bytes) -> io::Result<()> {
+        // Write to a temporary file first so readers never see a partial upload
+        let tmp_path = self.path(&format!("{}.tmp", key));
+        let mut file = fs::File::create(&tmp_path).await?;
+        file.write_all(&data).await?;
+        file.sync_all().await?;
+        fs::rename(&tmp_path, self.path(key)).await
+    }
+
+    async fn get(&self, key: &str, range: Range<u64>) -> io::Result<Body> {
+        let mut file = fs::File::open(self.path(key)).await?;
+        file.seek(SeekFrom::Start(range.start)).await?;
+        let reader = file.take(range.end.saturating_sub(range.start));
+        Ok(Body::from_stream(ReaderStream::new(reader)))
+    }
+
+    async fn delete(&self, key: &str) -> io::Result<()> {
+        match fs::remove_file(self.path(key)).await {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
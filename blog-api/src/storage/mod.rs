@@ -0,0 +1,21 @@
+pub mod local;
+
+use std::io;
+use std::ops::Range;
+
+use async_trait::async_trait;
+use axum::body::{Body, Bytes};
+
+pub use local::LocalMediaStore;
+
+// Backend for uploaded media files. Keys are generated by the application and
+// are safe to use as file names.
+#[async_trait]
+pub trait MediaStore: Send + Sync {
+    async fn put(&self, key: &str, data: Bytes) -> io::Result<()>;
+
+    // Streams the given byte range of a stored file
+    async fn get(&self, key: &str, range: Range<u64>) -> io::Result<Body>;
+
+    async fn delete(&self, key: &str) -> io::Result<()>;
+}
@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use axum::body::Body;
+use axum::http::{Method, Request, Response};
+use tower_http::classify::{ServerErrorsAsFailures, SharedClassifier};
+use tower_http::trace::{DefaultOnRequest, DefaultOnResponse, OnResponse, TraceLayer};
+use tracing::field::Empty;
+use tracing::Span;
+
+use super::otel;
+use crate::middleware::request_id::REQUEST_ID_HEADER;
+
+pub type HttpTraceLayer = TraceLayer<
+    SharedClassifier<ServerErrorsAsFailures>,
+    fn(&Request<Body>) -> Span,
+    DefaultOnRequest,
+    fn(&Response<Body>, Duration, &Span),
+>;
+
+/// Logs each request in a span carrying the request id and, when trace export is on,
+/// continues the caller's trace.
+pub fn trace_layer() -> HttpTraceLayer {
+    TraceLayer::new_for_http()
+        .make_span_with(make_span as fn(&Request<Body>) -> Span)
+        .on_response(on_response as fn(&Response<Body>, Duration, &Span))
+}
+
+// The query string is left out because it may contain personal data
+fn make_span(req: &Request<Body>) -> Span {
+    let request_id = req
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+
+    let span = tracing::info_span!(
+        "request",
+        http.request.method = %req.method(),
+        url.path = %req.uri().path(),
+        http.route = Empty,
+        request_id = %request_id,
+        http.response.status_code = Empty,
+        otel.name = Empty,
+        otel.kind = Empty,
+    );
+
+    if otel::enabled() {
+        span.record("otel.name", req.method().as_str());
+        span.record("otel.kind", "server");
+        otel::set_remote_parent(&span, req.headers());
+    }
+    span
+}
+
+/// Adds the matched route to the current request span. Called by `HttpMetricsLayer`,
+/// which finds the route wherever it is matched.
+pub fn record_route(method: &Method, route: &str) {
+    let span = Span::current();
+    span.record("http.route", route);
+    if otel::enabled() {
+        otel::rename(&span, format!("{} {}", method, route));
+    }
+}
+
+fn on_response(res: &Response<Body>, latency: Duration, span: &Span) {
+    span.record("http.response.status_code", res.status().as_u16());
+    DefaultOnResponse::new().on_response(res, latency, span);
+}
\ No newline at end of file
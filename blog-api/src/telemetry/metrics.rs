@@ -0,0 +1,174 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use ::metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram, Unit};
+use axum::extract::{MatchedPath, State};
+use axum::http::{header, Request, Response, StatusCode};
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use sqlx::PgPool;
+use tower::{Layer, Service};
+
+use crate::shutdown::Shutdown;
+
+const HTTP_REQUESTS: &str = "http_requests_total";
+const HTTP_DURATION: &str = "http_request_duration_seconds";
+const POOL_SIZE: &str = "db_pool_connections";
+const POOL_IDLE: &str = "db_pool_idle_connections";
+const POOL_WAIT: &str = "db_pool_acquire_wait_seconds";
+
+const DURATION_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+// Histogram samples are buffered until upkeep folds them into the buckets
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+// A slow acquire is reported as this long instead of holding up the scrape
+const POOL_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Installs the global Prometheus recorder. Metrics recorded before this are dropped.
+pub fn install(shutdown: &Shutdown) -> anyhow::Result<PrometheusHandle> {
+    let handle = PrometheusBuilder::new()
+        .set_buckets_for_metric(Matcher::Full(HTTP_DURATION.to_string()), DURATION_BUCKETS)?
+        .install_recorder()?;
+
+    describe_counter!(HTTP_REQUESTS, "HTTP requests by method, matched route and status");
+    describe_histogram!(HTTP_DURATION, Unit::Seconds, "HTTP request latency by method, matched route and status");
+    describe_gauge!(POOL_SIZE, "Open database connections");
+    describe_gauge!(POOL_IDLE, "Idle database connections");
+    describe_gauge!(POOL_WAIT, Unit::Seconds, "Time taken to acquire a database connection at scrape time");
+    describe_counter!("posts_created_total", "Posts created");
+    describe_counter!("posts_published_total", "Posts published, on creation or later");
+    describe_counter!("users_registered_total", "Users registered");
+
+    let upkeep = handle.clone();
+    let token = shutdown.token();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(UPKEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => upkeep.run_upkeep(),
+                _ = token.cancelled() => break,
+            }
+        }
+    });
+
+    Ok(handle)
+}
+
+#[derive(Clone)]
+struct MetricsState {
+    handle: PrometheusHandle,
+    pool: PgPool,
+}
+
+// Serves GET /metrics in the Prometheus text format
+pub fn create_router(handle: PrometheusHandle, pool: PgPool) -> Router {
+    Router::new()
+        .route("/metrics", get(render))
+        .with_state(MetricsState { handle, pool })
+}
+
+async fn render(State(state): State<MetricsState>) -> impl IntoResponse {
+    let pool = &state.pool;
+    gauge!(POOL_SIZE).set(pool.size() as f64);
+    gauge!(POOL_IDLE).set(pool.num_idle() as f64);
+
+    let started = Instant::now();
+    match tokio::time::timeout(POOL_WAIT_TIMEOUT, pool.acquire()).await {
+        Ok(Ok(_)) | Err(_) => gauge!(POOL_WAIT).set(started.elapsed().as_secs_f64()),
+        Ok(Err(e)) => tracing::warn!("Failed to acquire a connection for pool metrics: {}", e),
+    }
+
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        state.handle.render(),
+    )
+}
+
+/// Counts requests and records their latency, labelled by method, matched route and status.
+/// Apply it to the whole application with `Router::layer` so the matched route is known, and
+/// again inside routers served through `nest_service`, whose routes are only matched there.
+#[derive(Debug, Clone, Default)]
+pub struct HttpMetricsLayer;
+
+// Marks requests and responses that a layer further out or in has already measured
+#[derive(Debug, Clone, Copy)]
+struct Measured;
+
+impl<S> Layer<S> for HttpMetricsLayer {
+    type Service = HttpMetrics<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HttpMetrics { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpMetrics<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for HttpMetrics<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        if req.extensions().get::<Measured>().is_some() {
+            let future = self.inner.call(req);
+            return Box::pin(future);
+        }
+
+        let method = req.method().clone();
+        let route = req.extensions().get::<MatchedPath>().map(|p| p.as_str().to_string());
+        if let Some(route) = &route {
+            req.extensions_mut().insert(Measured);
+            super::http::record_route(&method, route);
+        }
+        let path = req.uri().path().to_string();
+        let started = Instant::now();
+        let future = self.inner.call(req);
+
+        Box::pin(async move {
+            let mut res = future.await?;
+            if res.extensions().get::<Measured>().is_some() {
+                return Ok(res);
+            }
+
+            let route = route.unwrap_or_else(|| unmatched_route(&path, res.status()));
+            let labels = [
+                ("method", method.to_string()),
+                ("route", route),
+                ("status", res.status().as_u16().to_string()),
+            ];
+            counter!(HTTP_REQUESTS, &labels).increment(1);
+            histogram!(HTTP_DURATION, &labels).record(started.elapsed().as_secs_f64());
+            res.extensions_mut().insert(Measured);
+            Ok(res)
+        })
+    }
+}
+
+// Services mounted with nest_service, such as the static files under /ui, only have the
+// prefix matched. Unknown paths all share one label to keep the label set bounded.
+fn unmatched_route(path: &str, status: StatusCode) -> String {
+    if status == StatusCode::NOT_FOUND {
+        return "unmatched".to_string();
+    }
+    match path.trim_start_matches('/').split('/').next() {
+        Some(prefix) if !prefix.is_empty() => format!("/{}/*", prefix),
+        _ => "unmatched".to_string(),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use axum::http::HeaderMap;
+use opentelemetry::global;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::config::OpenTelemetryConfig;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Builds the OTLP span exporter and enables W3C trace context propagation.
+/// Returns `None` when export is disabled, so no spans are converted or sent.
+pub fn init(config: &OpenTelemetryConfig) -> anyhow::Result<Option<SdkTracerProvider>> {
+    if !config.enabled {
+        return Ok(None);
+    }
+
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(&config.endpoint)
+        .build()?;
+
+    // Requests that arrive with a traceparent keep the caller's sampling decision
+    let sampler = Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(config.sample_ratio)));
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(sampler)
+        .with_resource(Resource::builder().with_service_name(config.service_name.clone()).build())
+        .build();
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    ENABLED.store(true, Ordering::Relaxed);
+
+    Ok(Some(provider))
+}
+
+// Whether spans are exported, so callers can skip work that only matters for export
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Continues the trace from the request's `traceparent` header, if any.
+pub fn set_remote_parent(span: &Span, headers: &HeaderMap) {
+    let context = global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)));
+    // Fails only when the span is disabled by the log filter
+    let _ = span.set_parent(context);
+}
+
+// The exported span has usually started by the time the route is known, after which
+// recording `otel.name` no longer renames it
+pub fn rename(span: &Span, name: String) {
+    span.context().span().update_name(name);
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
\ No newline at end of file
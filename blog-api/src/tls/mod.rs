@@ -0,0 +1,124 @@
+pub mod reload;
+
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::ConnectInfo;
+use axum::http::{header, uri::Authority, HeaderMap, Request, StatusCode, Uri};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::Router;
+use hyper::body::Incoming;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::server::graceful::GracefulShutdown;
+use tokio::net::TcpListener;
+use tokio_rustls::rustls::{self, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+use tower::ServiceExt;
+
+use crate::config::TlsConfig;
+use crate::shutdown::Shutdown;
+use reload::CertResolver;
+
+// Clients that connect but don't finish the handshake are dropped after this long
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Loads the certificate and key, failing if they can't be used, and starts watching the
+/// files for changes. HTTP/2 is offered through ALPN, with HTTP/1.1 as the fallback.
+pub async fn server_config(config: &TlsConfig, shutdown: &Shutdown) -> anyhow::Result<Arc<ServerConfig>> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let resolver = Arc::new(CertResolver::load(config, provider.clone()).await?);
+    reload::watch(resolver.clone(), Duration::from_secs(config.reload_interval_secs.max(1)), shutdown);
+
+    let mut server_config = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_no_client_auth()
+        .with_cert_resolver(resolver);
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(Arc::new(server_config))
+}
+
+/// Serves `app` over TLS until `signal` resolves, then stops accepting connections and
+/// waits for open ones to finish. Like `axum::serve` with connect info, requests carry
+/// the client address as `ConnectInfo<SocketAddr>`.
+pub async fn serve<F>(listener: TcpListener, app: Router, tls: Arc<ServerConfig>, signal: F) -> io::Result<()>
+where
+    F: Future<Output = ()>,
+{
+    let acceptor = TlsAcceptor::from(tls);
+    let builder = auto::Builder::new(TokioExecutor::new());
+    let graceful = GracefulShutdown::new();
+    let mut signal = std::pin::pin!(signal);
+
+    loop {
+        let (stream, remote) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    // Usually out of file descriptors; back off rather than spin
+                    tracing::error!("Failed to accept connection: {}", e);
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    continue;
+                }
+            },
+            _ = &mut signal => break,
+        };
+
+        let acceptor = acceptor.clone();
+        let builder = builder.clone();
+        let app = app.clone();
+        let watcher = graceful.watcher();
+
+        tokio::spawn(async move {
+            let stream = match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                Ok(Ok(stream)) => stream,
+                Ok(Err(e)) => {
+                    tracing::debug!("TLS handshake with {} failed: {}", remote, e);
+                    return;
+                }
+                Err(_) => {
+                    tracing::debug!("TLS handshake with {} timed out", remote);
+                    return;
+                }
+            };
+
+            let service = hyper::service::service_fn(move |mut req: Request<Incoming>| {
+                req.extensions_mut().insert(ConnectInfo(remote));
+                app.clone().oneshot(req)
+            });
+            let conn = builder.serve_connection_with_upgrades(TokioIo::new(stream), service);
+            if let Err(e) = watcher.watch(conn).await {
+                tracing::debug!("Connection from {} closed with an error: {}", remote, e);
+            }
+        });
+    }
+
+    drop(listener);
+    graceful.shutdown().await;
+    Ok(())
+}
+
+/// Redirects every plain HTTP request to the same host and path over HTTPS on `https_port`.
+pub fn redirect_router(https_port: u16) -> Router {
+    Router::new().fallback(move |uri: Uri, headers: HeaderMap| async move { redirect(&uri, &headers, https_port) })
+}
+
+fn redirect(uri: &Uri, headers: &HeaderMap, https_port: u16) -> Response {
+    let host = headers
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<Authority>().ok());
+    let Some(host) = host else {
+        return (StatusCode::BAD_REQUEST, "Missing or invalid Host header").into_response();
+    };
+
+    let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+    let location = match https_port {
+        443 => format!("https://{}{}", host.host(), path),
+        port => format!("https://{}:{}{}", host.host(), port, path),
+    };
+    Redirect::permanent(&location).into_response()
+}
\ No newline at end of file
@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use tokio_rustls::rustls::crypto::CryptoProvider;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::server::{ClientHello, ResolvesServerCert};
+use tokio_rustls::rustls::sign::CertifiedKey;
+
+use crate::config::TlsConfig;
+use crate::shutdown::Shutdown;
+
+/// Hands every handshake the most recently loaded certificate, so a renewed certificate
+/// is picked up without restarting or dropping open connections.
+#[derive(Debug)]
+pub struct CertResolver {
+    cert_path: PathBuf,
+    key_path: PathBuf,
+    provider: Arc<CryptoProvider>,
+    current: RwLock<Arc<CertifiedKey>>,
+}
+
+impl CertResolver {
+    pub async fn load(config: &TlsConfig, provider: Arc<CryptoProvider>) -> anyhow::Result<Self> {
+        let cert_path = PathBuf::from(&config.cert_path);
+        let key_path = PathBuf::from(&config.key_path);
+        let key = read(&cert_path, &key_path, &provider).await?;
+
+        Ok(Self {
+            cert_path,
+            key_path,
+            provider,
+            current: RwLock::new(Arc::new(key)),
+        })
+    }
+
+    async fn reload(&self) -> anyhow::Result<()> {
+        let key = read(&self.cert_path, &self.key_path, &self.provider).await?;
+        *self.current.write().expect("certificate lock poisoned") = Arc::new(key);
+        Ok(())
+    }
+}
+
+impl ResolvesServerCert for CertResolver {
+    fn resolve(&self, _client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        Some(self.current.read().expect("certificate lock poisoned").clone())
+    }
+}
+
+async fn read(cert_path: &Path, key_path: &Path, provider: &CryptoProvider) -> anyhow::Result<CertifiedKey> {
+    let cert_pem = tokio::fs::read(cert_path)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", cert_path.display(), e))?;
+    let key_pem = tokio::fs::read(key_path)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", key_path.display(), e))?;
+
+    let certs = CertificateDer::pem_slice_iter(&cert_pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("Invalid certificate in {}: {}", cert_path.display(), e))?;
+    if certs.is_empty() {
+        anyhow::bail!("No certificate found in {}", cert_path.display());
+    }
+    let key = PrivateKeyDer::from_pem_slice(&key_pem)
+        .map_err(|e| anyhow::anyhow!("Invalid private key in {}: {}", key_path.display(), e))?;
+
+    // Also rejects a key that doesn't belong to the certificate, which happens when only
+    // one of the files has been replaced so far
+    Ok(CertifiedKey::from_der(certs, key, provider)?)
+}
+
+// Modification time and size of a file, used to notice when it has been replaced
+type Fingerprint = Option<(SystemTime, u64)>;
+
+async fn fingerprint(path: &Path) -> Fingerprint {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Polls the certificate and key files and reloads them when either changes. A pair that
+/// fails to load keeps the previous certificate in use and is tried again on the next poll.
+pub fn watch(resolver: Arc<CertResolver>, interval: Duration, shutdown: &Shutdown) {
+    let token = shutdown.token();
+
+    shutdown.spawn(async move {
+        let mut loaded = (
+            fingerprint(&resolver.cert_path).await,
+            fingerprint(&resolver.key_path).await,
+        );
+        let mut interval = tokio::time::interval(interval);
+        interval.tick().await;
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = token.cancelled() => break,
+            }
+
+            let current = (
+                fingerprint(&resolver.cert_path).await,
+                fingerprint(&resolver.key_path).await,
+            );
+            if current == loaded {
+                continue;
+            }
+
+            match resolver.reload().await {
+                Ok(()) => {
+                    tracing::info!("Reloaded TLS certificate from {}", resolver.cert_path.display());
+                    loaded = current;
+                }
+                Err(e) => tracing::warn!("Failed to reload TLS certificate, keeping the current one: {}", e),
+            }
+        }
+    });
+}
\ No newline at end of file
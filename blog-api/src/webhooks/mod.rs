@@ -0,0 +1,259 @@
+pub mod target;
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use serde::Serialize;
+use sha2::Sha256;
+use sqlx::PgPool;
+use tokio::sync::Notify;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::config::WebhooksConfig;
+use crate::db::webhook_repository::{DeliveryAttempt, DueDelivery};
+use crate::db::WebhookRepository;
+use crate::errors::{AppError, Result};
+use crate::models::webhook::{WebhookDelivery, EVENT_PING, STATUS_FAILED, STATUS_PENDING, STATUS_SUCCEEDED};
+use crate::shutdown::Shutdown;
+
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+
+// Signs `{timestamp}.{body}` so receivers can reject both forged and replayed requests
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+pub fn generate_secret() -> String {
+    let bytes: [u8; 32] = rand::thread_rng().gen();
+    hex::encode(bytes)
+}
+
+// Outgoing webhooks. Events are written to the webhook_deliveries table first and sent by a
+// background task, so deliveries survive restarts and failed ones are retried with backoff.
+#[derive(Clone)]
+pub struct Webhooks {
+    pool: PgPool,
+    wake: Arc<Notify>,
+    allow_private_targets: bool,
+}
+
+#[derive(Serialize)]
+struct Envelope<'a, T: Serialize> {
+    id: Uuid,
+    event: &'a str,
+    created_at: chrono::DateTime<Utc>,
+    data: T,
+}
+
+impl Webhooks {
+    pub fn start(pool: PgPool, config: WebhooksConfig, shutdown: &Shutdown) -> Self {
+        let webhooks = Self {
+            pool,
+            wake: Arc::new(Notify::new()),
+            allow_private_targets: config.allow_private_targets,
+        };
+
+        let sender = Sender::new(webhooks.pool.clone(), webhooks.wake.clone(), config);
+        shutdown.spawn(sender.run(shutdown.token()));
+
+        webhooks
+    }
+
+    // Queues the event for all subscribers. Failures are logged rather than returned, since
+    // the change that caused the event has already been made.
+    pub async fn publish<T: Serialize>(&self, event: &str, data: T) {
+        let payload = match envelope(event, data) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!("Failed to serialize {} webhook payload: {}", event, e);
+                return;
+            }
+        };
+
+        match WebhookRepository::new(self.pool.clone()).enqueue(event, &payload).await {
+            Ok(0) => {}
+            Ok(_) => self.wake.notify_one(),
+            Err(e) => tracing::error!("Failed to queue {} webhooks: {}", event, e),
+        }
+    }
+
+    // Queues a ping for a single subscription, to check that its receiver works
+    pub async fn ping(&self, subscription_id: Uuid) -> Result<WebhookDelivery> {
+        let payload = envelope(EVENT_PING, serde_json::json!({ "subscription_id": subscription_id }))
+            .map_err(|e| anyhow::anyhow!(e))?;
+        let delivery = WebhookRepository::new(self.pool.clone())
+            .enqueue_for(subscription_id, EVENT_PING, &payload)
+            .await?;
+        self.wake.notify_one();
+
+        Ok(delivery)
+    }
+
+    // Rejects receiver URLs the sender would refuse to deliver to
+    pub fn check_url(&self, url: &str) -> Result<()> {
+        target::check_url(url, self.allow_private_targets).map_err(AppError::BadRequest)?;
+        Ok(())
+    }
+
+    // Lets the sender know that a delivery was queued again
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+}
+
+fn envelope<T: Serialize>(event: &str, data: T) -> serde_json::Result<serde_json::Value> {
+    serde_json::to_value(Envelope {
+        id: Uuid::new_v4(),
+        event,
+        created_at: Utc::now(),
+        data,
+    })
+}
+
+struct Sender {
+    pool: PgPool,
+    wake: Arc<Notify>,
+    client: reqwest::Client,
+    config: Arc<WebhooksConfig>,
+}
+
+impl Sender {
+    fn new(pool: PgPool, wake: Arc<Notify>, config: WebhooksConfig) -> Self {
+        // Redirects aren't followed, since they could lead to an address the checks would refuse
+        let mut client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent(concat!("blog-api-webhooks/", env!("CARGO_PKG_VERSION")))
+            .redirect(reqwest::redirect::Policy::none());
+        if !config.allow_private_targets {
+            client = client.dns_resolver(Arc::new(target::PublicResolver));
+        }
+        let client = client.build().expect("Failed to build the webhook HTTP client");
+
+        Self {
+            pool,
+            wake,
+            client,
+            config: Arc::new(config),
+        }
+    }
+
+    // Sends finish once cancelled, but no new batch is claimed. Deliveries claimed and cut
+    // off by the shutdown deadline are retried after their lease runs out.
+    async fn run(self, token: CancellationToken) {
+        let repo = WebhookRepository::new(self.pool.clone());
+        let poll_interval = Duration::from_secs(self.config.poll_interval_secs.max(1));
+        // Long enough that a claimed delivery isn't picked up again while its request is running
+        let lease_secs = (self.config.timeout_secs + 30) as f64;
+
+        while !token.is_cancelled() {
+            let due = match repo.claim_due(self.config.batch_size, lease_secs).await {
+                Ok(due) => due,
+                Err(e) => {
+                    tracing::error!("Failed to load due webhook deliveries: {}", e);
+                    Vec::new()
+                }
+            };
+
+            if due.is_empty() {
+                tokio::select! {
+                    _ = self.wake.notified() => {}
+                    _ = tokio::time::sleep(poll_interval) => {}
+                    _ = token.cancelled() => {}
+                }
+                continue;
+            }
+
+            let mut sends = JoinSet::new();
+            for delivery in due {
+                let client = self.client.clone();
+                let pool = self.pool.clone();
+                let config = self.config.clone();
+                sends.spawn(async move { deliver(&client, &pool, &config, delivery).await });
+            }
+            while sends.join_next().await.is_some() {}
+        }
+    }
+}
+
+async fn deliver(client: &reqwest::Client, pool: &PgPool, config: &WebhooksConfig, delivery: DueDelivery) {
+    let body = delivery.payload.to_string().into_bytes();
+    let timestamp = Utc::now().timestamp();
+    let signature = sign(&delivery.secret, timestamp, &body);
+
+    let started = Instant::now();
+    // IP literals never reach the resolver, so the URL is checked again on every attempt
+    let result = match target::check_url(&delivery.url, config.allow_private_targets) {
+        Ok(url) => client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &delivery.event)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .body(body)
+            .send()
+            .await
+            // With the causes, e.g. that the host only resolves to private addresses
+            .map_err(|e| format!("{:#}", anyhow::Error::from(e))),
+        Err(e) => Err(e),
+    };
+    let duration_ms = started.elapsed().as_millis().min(i32::MAX as u128) as i32;
+
+    let (status_code, error) = match result {
+        Ok(response) if response.status().is_success() => (Some(response.status().as_u16() as i32), None),
+        Ok(response) => (
+            Some(response.status().as_u16() as i32),
+            Some(format!("Receiver responded with {}", response.status())),
+        ),
+        Err(e) => (None, Some(e)),
+    };
+
+    let attempts = delivery.attempts as u32 + 1;
+    let (status, next_attempt_at) = if error.is_none() {
+        (STATUS_SUCCEEDED, Utc::now())
+    } else if attempts >= config.max_attempts {
+        (STATUS_FAILED, Utc::now())
+    } else {
+        let delay = backoff(config, attempts);
+        (STATUS_PENDING, Utc::now() + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX))
+    };
+
+    if let Some(error) = &error {
+        tracing::warn!("Webhook delivery {} to {} failed (attempt {}): {}", delivery.id, delivery.url, attempts, error);
+    }
+
+    let attempt = DeliveryAttempt {
+        status_code,
+        error: error.as_deref(),
+        duration_ms,
+    };
+    if let Err(e) = WebhookRepository::new(pool.clone())
+        .record_attempt(delivery.id, &attempt, status, next_attempt_at)
+        .await
+    {
+        tracing::error!("Failed to record webhook delivery {}: {}", delivery.id, e);
+    }
+}
+
+// Exponential backoff with up to 20% jitter, so retries from one outage don't arrive in lockstep
+fn backoff(config: &WebhooksConfig, attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(30);
+    let secs = config
+        .initial_backoff_secs
+        .saturating_mul(1u64 << exponent)
+        .min(config.max_backoff_secs);
+    let jitter = rand::thread_rng().gen_range(0.8..=1.2);
+    Duration::from_secs_f64(secs as f64 * jitter)
+}
\ No newline at end of file
@@ -0,0 +1,107 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::Url;
+
+/// Checks that a webhook URL uses http or https and doesn't name this host or a private,
+/// loopback or link-local address. Host names are checked again whenever a delivery
+/// resolves them, see `PublicResolver`.
+pub fn check_url(url: &str, allow_private: bool) -> Result<Url, String> {
+    let url = Url::parse(url).map_err(|e| format!("Invalid webhook URL: {}", e))?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err("Webhook URLs must use http or https".to_string());
+    }
+
+    let host = url.host_str().ok_or_else(|| "Webhook URLs must have a host".to_string())?;
+    if allow_private {
+        return Ok(url);
+    }
+
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    match host.parse::<IpAddr>() {
+        Ok(ip) if !is_public(ip) => return Err(format!("Webhook URLs can't point at {}", ip)),
+        Ok(_) => {}
+        Err(_) => {
+            let name = host.trim_end_matches('.').to_ascii_lowercase();
+            if name == "localhost" || name.ends_with(".localhost") {
+                return Err(format!("Webhook URLs can't point at {}", host));
+            }
+        }
+    }
+
+    Ok(url)
+}
+
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                || a == 0
+                // Carrier-grade NAT, 100.64.0.0/10
+                || (a == 100 && (b & 0xc0) == 64))
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = embedded_ipv4(ip) {
+                return is_public(IpAddr::V4(v4));
+            }
+            let [a, b, ..] = ip.segments();
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || ip.is_unique_local()
+                || ip.is_unicast_link_local()
+                // Documentation, 2001:db8::/32
+                || (a == 0x2001 && b == 0x0db8)
+                // Local-use NAT64, 64:ff9b:1::/48
+                || (a == 0x64 && b == 0xff9b))
+        }
+    }
+}
+
+// The IPv4 address that an IPv6 address forwards to, so those forms can't be used to reach
+// an internal IPv4 address
+fn embedded_ipv4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    let segments = ip.segments();
+    let [_, _, _, _, _, _, c, d] = segments;
+    let last = Ipv4Addr::new((c >> 8) as u8, c as u8, (d >> 8) as u8, d as u8);
+    match segments {
+        // IPv4-mapped, ::ffff:a.b.c.d
+        [0, 0, 0, 0, 0, 0xffff, _, _] => Some(last),
+        // IPv4-compatible, ::a.b.c.d, except :: and ::1
+        [0, 0, 0, 0, 0, 0, _, _] if !ip.is_unspecified() && !ip.is_loopback() => Some(last),
+        // NAT64, 64:ff9b::a.b.c.d
+        [0x64, 0xff9b, 0, 0, 0, 0, _, _] => Some(last),
+        // 6to4, 2002:aabb:ccdd::/48
+        [0x2002, a, b, ..] => Some(Ipv4Addr::new((a >> 8) as u8, a as u8, (b >> 8) as u8, b as u8)),
+        // Teredo, 2001::/32, with the client address stored inverted
+        [0x2001, 0, ..] => Some(Ipv4Addr::from(!u32::from(last))),
+        _ => None,
+    }
+}
+
+/// DNS resolver for the webhook client that only hands out public addresses, so a receiver
+/// can't point its host name at internal services after the URL has been accepted.
+#[derive(Debug)]
+pub struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let public: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public(addr.ip()))
+                .collect();
+            if public.is_empty() {
+                return Err(format!("{} does not resolve to a public address", name.as_str()).into());
+            }
+            Ok(Box::new(public.into_iter()) as Addrs)
+        })
+    }
+}
\ No newline at end of file